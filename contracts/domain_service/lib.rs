@@ -1,20 +1,226 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+mod psp34;
+
 #[ink::contract]
 mod domain_service {
+    use crate::psp34::{Id, PSP34Enumerable, PSP34Error, PSP34Metadata, PSP34};
+    use ink::env::hash::Blake2x256;
+    use ink::prelude::format;
     use ink::prelude::string::String;
+    use ink::prelude::vec;
+    use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
-    use utils::MultilocationEncoded;
+    use utils::DomainServiceError as Error;
+    use utils::{normalize_name, FeeSplit, NameRules};
+    use utils::{MultilocationEncoded, TargetChain, VersionedMultilocationEncoded};
+
+    /// Default length of a registration year in blocks, assuming 12s block time
+    pub const DEFAULT_REGISTRATION_PERIOD: BlockNumber = 2_628_000;
+    /// Default grace period after expiry, assuming 12s block time (~90 days)
+    pub const DEFAULT_GRACE_PERIOD: BlockNumber = 648_000;
+    /// Default yearly price of a name, irrespective of its length
+    pub const DEFAULT_PRICE: Balance = 100;
+    /// Maximum number of per-chain address records of a name
+    pub const MAX_ADDRESS_RECORDS: usize = 16;
+    /// Maximum number of text records of a name
+    pub const MAX_RECORDS: usize = 16;
+    /// Maximum length of a text record key, in bytes
+    pub const MAX_RECORD_KEY_LENGTH: usize = 32;
+    /// Maximum length of a text record value, in bytes
+    pub const MAX_RECORD_VALUE_LENGTH: usize = 256;
+    /// Maximum number of subdomains of a name
+    pub const MAX_SUBDOMAINS: usize = 16;
+
+    // Events of name management carry `via_xcm`, set for requests relayed by
+    // the xcm-handler, along with the location of the requesting xc-contract.
+    // `name_hash` is the Blake2x256 hash of the normalized name.
+
+    #[ink(event)]
+    pub struct NameRegistered {
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        name: String,
+        #[ink(topic)]
+        owner: AccountId,
+        expiry: BlockNumber,
+        price: Balance,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    }
+
+    #[ink(event)]
+    pub struct NameRenewed {
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        name: String,
+        expiry: BlockNumber,
+        price: Balance,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    }
+
+    #[ink(event)]
+    pub struct NameTransferred {
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        name: String,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    }
+
+    /// `chain` is `None` for the default address, `address` is `None` once removed
+    #[ink(event)]
+    pub struct AddressSet {
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        name: String,
+        chain: Option<TargetChain>,
+        address: Option<VersionedMultilocationEncoded>,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    }
+
+    #[ink(event)]
+    pub struct RecordsUpdated {
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        name: String,
+        /// Keys of the records set or removed
+        keys: Vec<String>,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    }
+
+    /// `name` is the full name of the subdomain, e.g. `pay.alice`
+    #[ink(event)]
+    pub struct SubdomainOwnerSet {
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        name: String,
+        #[ink(topic)]
+        owner: AccountId,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    }
+
+    #[ink(event)]
+    pub struct SubdomainRevoked {
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        name: String,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    }
+
+    #[ink(event)]
+    pub struct SubdomainLocked {
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        name: String,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    }
+
+    #[ink(event)]
+    pub struct PrimaryNameSet {
+        #[ink(topic)]
+        account: AccountId,
+        name: Option<String>,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    }
+
+    #[ink(event)]
+    pub struct ManagerSet {
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        name: String,
+        #[ink(topic)]
+        manager: Option<AccountId>,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    }
+
+    #[ink(event)]
+    pub struct OperatorSet {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        approved: bool,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    }
+
+    #[ink(event)]
+    pub struct HandlerChanged {
+        #[ink(topic)]
+        old_handler: AccountId,
+        #[ink(topic)]
+        new_handler: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct PeriodsChanged {
+        registration_period: BlockNumber,
+        grace_period: BlockNumber,
+    }
+
+    #[ink(event)]
+    pub struct PriceConfigChanged {
+        price_tiers: Vec<Balance>,
+        year_multipliers: Vec<u32>,
+    }
+
+    #[ink(event)]
+    pub struct NameRulesChanged {
+        name_rules: NameRules,
+    }
+
+    #[ink(event)]
+    pub struct FeeSplitChanged {
+        fee_split: FeeSplit,
+    }
+
+    #[ink(event)]
+    pub struct ReferralPaid {
+        #[ink(topic)]
+        referrer: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Withdrawn {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    /// PSP34 event, emitted whenever a name is registered, transferred or removed
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        id: Id,
+    }
 
-    #[derive(scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub enum Error {
-        NotAdmin,
-        NotHandler,
-        NotOwner,
-        NameAlreadyExists,
-        NameDoesNotExists,
-        PaymentNotReceived,
+    /// PSP34 event, emitted whenever a manager (`id` is the name) or an operator
+    /// (`id` is `None`) is appointed or removed
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        id: Option<Id>,
+        approved: bool,
     }
 
     #[ink(storage)]
@@ -22,7 +228,50 @@ mod domain_service {
         admin: AccountId,
         handler: AccountId,
         name_to_owner: Mapping<String, AccountId>,
+        /// Addresses set before versioned locations were introduced, read-only
         name_to_multilocation: Mapping<String, MultilocationEncoded>,
+        name_to_location: Mapping<String, VersionedMultilocationEncoded>,
+        /// Per-chain address records, used instead of the default address on the given chain
+        name_to_chain_address: Mapping<(String, TargetChain), VersionedMultilocationEncoded>,
+        name_to_chains: Mapping<String, Vec<TargetChain>>,
+        /// Text records (e.g. avatar, url, email, twitter) as key-value pairs
+        name_to_records: Mapping<String, Vec<(String, String)>>,
+        /// Labels of the subdomains of a name, e.g. `pay` for `pay.alice`.
+        /// Subdomains are stored by their full name and expire along with their parent.
+        name_to_subdomains: Mapping<String, Vec<String>>,
+        /// Subdomains the parent owner can no longer reassign or revoke
+        locked_subdomains: Mapping<String, ()>,
+        /// Primary name of an account, for reverse resolution, along with the location
+        /// of the account the name must resolve to
+        account_to_primary_name: Mapping<AccountId, (String, MultilocationEncoded)>,
+        name_to_expiry: Mapping<String, BlockNumber>,
+        registration_period: BlockNumber,
+        grace_period: BlockNumber,
+        /// Yearly price by name length: entry `i` applies to names of length `i + 1`,
+        /// the last entry applies to all longer names
+        price_tiers: Vec<Balance>,
+        /// Percentage of the yearly price charged for the n-th year of a registration,
+        /// the last entry applies to all subsequent years
+        year_multipliers: Vec<u32>,
+        /// Rules names must comply with once normalized
+        name_rules: NameRules,
+        /// Names held by an account, expired ones included until reclaimed
+        owner_to_names: Mapping<(AccountId, u32), String>,
+        name_to_owner_index: Mapping<String, u32>,
+        owner_name_count: Mapping<AccountId, u32>,
+        /// Enumeration of all the names, as PSP34 tokens
+        token_index: Mapping<u128, String>,
+        name_to_token_index: Mapping<String, u128>,
+        total_tokens: u128,
+        /// Manager of a single name, allowed to transfer it and update its addresses
+        /// on behalf of its owner. Doubles as the PSP34 token approval.
+        token_approvals: Mapping<String, AccountId>,
+        /// (owner, operator) pairs where the operator may manage all names of the owner
+        operator_approvals: Mapping<(AccountId, AccountId), ()>,
+        /// How payments for names are shared out between treasury, referrer & locked shares
+        fee_split: FeeSplit,
+        /// Locked share of the payments, kept in the contract for good
+        locked: Balance,
     }
 
     impl DomainService {
@@ -33,6 +282,29 @@ mod domain_service {
                 handler,
                 name_to_owner: Mapping::default(),
                 name_to_multilocation: Mapping::default(),
+                name_to_location: Mapping::default(),
+                name_to_chain_address: Mapping::default(),
+                name_to_chains: Mapping::default(),
+                name_to_records: Mapping::default(),
+                name_to_subdomains: Mapping::default(),
+                locked_subdomains: Mapping::default(),
+                account_to_primary_name: Mapping::default(),
+                name_to_expiry: Mapping::default(),
+                registration_period: DEFAULT_REGISTRATION_PERIOD,
+                grace_period: DEFAULT_GRACE_PERIOD,
+                price_tiers: vec![DEFAULT_PRICE],
+                year_multipliers: vec![100],
+                name_rules: NameRules::default(),
+                owner_to_names: Mapping::default(),
+                name_to_owner_index: Mapping::default(),
+                owner_name_count: Mapping::default(),
+                token_index: Mapping::default(),
+                name_to_token_index: Mapping::default(),
+                total_tokens: 0,
+                token_approvals: Mapping::default(),
+                operator_approvals: Mapping::default(),
+                fee_split: FeeSplit::default(),
+                locked: 0,
             }
         }
 
@@ -49,43 +321,321 @@ mod domain_service {
             self.handler
         }
 
+        /// Returns (registration-period, grace-period) in blocks
+        #[ink(message)]
+        pub fn get_periods(&self) -> (BlockNumber, BlockNumber) {
+            (self.registration_period, self.grace_period)
+        }
+
+        /// Returns (price-tiers, year-multipliers)
+        #[ink(message)]
+        pub fn get_price_config(&self) -> (Vec<Balance>, Vec<u32>) {
+            (self.price_tiers.clone(), self.year_multipliers.clone())
+        }
+
+        /// Returns the rules names must comply with once normalized
+        #[ink(message)]
+        pub fn get_name_rules(&self) -> NameRules {
+            self.name_rules.clone()
+        }
+
+        /// Returns how payments for names are shared out
+        #[ink(message)]
+        pub fn get_fee_split(&self) -> FeeSplit {
+            self.fee_split
+        }
+
+        /// Returns the balance the admin can withdraw, i.e. excluding the locked share
+        #[ink(message)]
+        pub fn get_withdrawable_balance(&self) -> Balance {
+            self.env().balance().saturating_sub(self.locked)
+        }
+
+        /// Returns the price of registering (or renewing) the name for given number of years
+        #[ink(message)]
+        pub fn get_price(&self, name: String, years: u8) -> Balance {
+            let name = normalize_name(&name);
+            let len = name.chars().count().max(1);
+            let tier = len.min(self.price_tiers.len());
+            let yearly_price = self.price_tiers[tier - 1];
+
+            (1..=years as usize)
+                .map(|year| {
+                    let idx = year.min(self.year_multipliers.len()) - 1;
+                    let multiplier = self.year_multipliers[idx] as Balance;
+                    yearly_price.saturating_mul(multiplier) / 100
+                })
+                .fold(0, Balance::saturating_add)
+        }
+
+        /// Returns the owner of the name, if the registration has not expired
         #[ink(message)]
         pub fn get_owner(&self, name: String) -> Option<AccountId> {
+            let name = normalize_name(&name);
+            if !self.is_active(&name) {
+                return None;
+            }
             self.name_to_owner.get(name)
         }
 
+        /// Returns the primary name of the account, as long as the account still owns it
+        /// and the name still resolves to the account
+        #[ink(message)]
+        pub fn get_primary_name(&self, account: AccountId) -> Option<String> {
+            let (name, location) = self.account_to_primary_name.get(account)?;
+            (self.get_owner(name.clone()) == Some(account) && self.resolves_to(&name, &location))
+                .then_some(name)
+        }
+
+        /// Returns the resolving address of the name, if the registration has not expired
         #[ink(message)]
         pub fn get_address(&self, name: String) -> Option<xcm::VersionedMultiLocation> {
-            self.name_to_multilocation.get(name).map(Into::into)
+            let name = normalize_name(&name);
+            if !self.is_active(&name) {
+                return None;
+            }
+            self.get_location(&name)
+                .and_then(|loc| xcm::v3::MultiLocation::try_from(loc).ok())
+                .map(Into::into)
+        }
+
+        /// Returns the address record of the name for the given chain, if the registration
+        /// has not expired. Does not fall back to the default address.
+        #[ink(message)]
+        pub fn get_address_for(
+            &self,
+            name: String,
+            chain: TargetChain,
+        ) -> Option<xcm::VersionedMultiLocation> {
+            let name = normalize_name(&name);
+            if !self.is_active(&name) {
+                return None;
+            }
+            self.name_to_chain_address
+                .get((name, chain))
+                .and_then(|loc| xcm::v3::MultiLocation::try_from(loc).ok())
+                .map(Into::into)
+        }
+
+        /// Returns all the per-chain address records of the name, if the registration
+        /// has not expired
+        #[ink(message)]
+        pub fn get_all_addresses(
+            &self,
+            name: String,
+        ) -> Vec<(TargetChain, xcm::VersionedMultiLocation)> {
+            let name = normalize_name(&name);
+            self.get_chains(&name)
+                .into_iter()
+                .filter_map(|chain| {
+                    let loc = self.get_address_for(name.clone(), chain)?;
+                    Some((chain, loc))
+                })
+                .collect()
+        }
+
+        /// Returns the text records of the name, if the registration has not expired
+        #[ink(message)]
+        pub fn get_records(&self, name: String) -> Vec<(String, String)> {
+            let name = normalize_name(&name);
+            if !self.is_active(&name) {
+                return Vec::new();
+            }
+            self.name_to_records.get(name).unwrap_or_default()
+        }
+
+        /// Returns the value of the text record `key` of the name,
+        /// if the registration has not expired
+        #[ink(message)]
+        pub fn get_record(&self, name: String, key: String) -> Option<String> {
+            self.get_records(name)
+                .into_iter()
+                .find_map(|(k, v)| (k == key).then_some(v))
+        }
+
+        /// Returns the block number at which the registration expires.
+        /// The owner can still renew the name until `expiry + grace_period`.
+        #[ink(message)]
+        pub fn get_expiry(&self, name: String) -> Option<BlockNumber> {
+            let name = normalize_name(&name);
+            match Self::parent_of(&name) {
+                Some(parent) if self.name_to_owner.contains(&name) => {
+                    self.name_to_expiry.get(parent)
+                }
+                Some(_) => None,
+                None => self.name_to_expiry.get(name),
+            }
+        }
+
+        /// Returns the labels of the subdomains of the name, if the registration has not expired
+        #[ink(message)]
+        pub fn get_subdomains(&self, name: String) -> Vec<String> {
+            let name = normalize_name(&name);
+            if !self.is_active(&name) {
+                return Vec::new();
+            }
+            self.name_to_subdomains.get(name).unwrap_or_default()
+        }
+
+        /// Returns true if the parent owner can no longer reassign or revoke the subdomain
+        #[ink(message)]
+        pub fn is_subdomain_locked(&self, name: String) -> bool {
+            let name = normalize_name(&name);
+            self.locked_subdomains.contains(name)
+        }
+
+        /// Returns the manager of the name, if the registration has not expired
+        #[ink(message)]
+        pub fn get_manager(&self, name: String) -> Option<AccountId> {
+            let name = normalize_name(&name);
+            if !self.is_active(&name) {
+                return None;
+            }
+            self.token_approvals.get(name)
+        }
+
+        /// Returns true if `operator` may manage all the names of `owner`
+        #[ink(message)]
+        pub fn is_operator(&self, owner: AccountId, operator: AccountId) -> bool {
+            self.operator_approvals.contains((owner, operator))
         }
 
         /** Getters ENDS here */
 
         /** Setters for NATIVE calls STARTS here */
 
+        /// Registers the name for given number of years.
+        /// Payment must cover `get_price(name, years)`.
         #[ink(message, payable)]
-        pub fn register_name(&mut self, name: String) -> Result<(), Error> {
+        pub fn register_name(&mut self, name: String, years: u8) -> Result<(), Error> {
             let caller = self.env().caller();
-            if self.env().transferred_value() < 100 {
-                return Err(Error::PaymentNotReceived);
-            }
-            self.do_register_name(&caller, &name)
+            let paid = self.env().transferred_value();
+            let price = self.do_register_name(&caller, None, &name, years, paid)?;
+            self.split_payment(price, None);
+            Ok(())
+        }
+
+        /// Same as `register_name`, with the referrer's share of the payment
+        /// (see `get_fee_split`) paid out to `referrer`
+        #[ink(message, payable)]
+        pub fn register_name_with_referrer(
+            &mut self,
+            name: String,
+            years: u8,
+            referrer: AccountId,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let paid = self.env().transferred_value();
+            let price = self.do_register_name(&caller, None, &name, years, paid)?;
+            self.split_payment(price, Some(referrer));
+            Ok(())
+        }
+
+        /// Extends the registration by given number of years.
+        /// Only the owner can renew, either before expiry or within the grace period.
+        #[ink(message, payable)]
+        pub fn renew_name(&mut self, name: String, years: u8) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let paid = self.env().transferred_value();
+            let price = self.do_renew_name(&caller, None, &name, years, paid)?;
+            self.split_payment(price, None);
+            Ok(())
         }
 
         #[ink(message)]
         pub fn transfer_name(&mut self, name: String, receiver: AccountId) -> Result<(), Error> {
             let caller = self.env().caller();
-            self.do_transfer_name(&caller, &name, &receiver)
+            self.do_transfer_name(&caller, None, &name, &receiver)
         }
 
         #[ink(message)]
         pub fn set_address(
             &mut self,
             name: String,
-            loc: MultilocationEncoded,
+            loc: VersionedMultilocationEncoded,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.do_set_address(&caller, None, &name, &loc)
+        }
+
+        /// Sets the address record of the name for the given chain, `None` removes it
+        #[ink(message)]
+        pub fn set_address_for(
+            &mut self,
+            name: String,
+            chain: TargetChain,
+            loc: Option<VersionedMultilocationEncoded>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.do_set_address_for(&caller, None, &name, chain, loc)
+        }
+
+        /// Updates the text records of the name: `Some(value)` sets the record `key`,
+        /// `None` removes it. Other records are left untouched.
+        #[ink(message)]
+        pub fn set_records(
+            &mut self,
+            name: String,
+            records: Vec<(String, Option<String>)>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.do_set_records(&caller, None, &name, records)
+        }
+
+        /// Creates the subdomain `label.name` or reassigns it to `owner`.
+        /// Only the owner of the name can call, as long as the subdomain is not locked.
+        #[ink(message)]
+        pub fn set_subdomain_owner(
+            &mut self,
+            name: String,
+            label: String,
+            owner: AccountId,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.do_set_subdomain_owner(&caller, None, &name, &label, &owner)
+        }
+
+        /// Removes the subdomain `label.name` along with its data.
+        /// Only the owner of the name can call, as long as the subdomain is not locked.
+        #[ink(message)]
+        pub fn revoke_subdomain(&mut self, name: String, label: String) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.do_revoke_subdomain(&caller, None, &name, &label)
+        }
+
+        /// Locks the subdomain `label.name` for good, so that the owner of the name
+        /// can no longer reassign or revoke it. The subdomain still expires with its parent.
+        #[ink(message)]
+        pub fn lock_subdomain(&mut self, name: String, label: String) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.do_lock_subdomain(&caller, None, &name, &label)
+        }
+
+        /// Sets the primary name of the caller, which must own the name. `None` unsets it.
+        #[ink(message)]
+        pub fn set_primary_name(&mut self, name: Option<String>) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let location = MultilocationEncoded::new(0, None, caller);
+            self.do_set_primary_name(&caller, None, &location, name)
+        }
+
+        /// Sets the manager of the name, who can transfer it and update its addresses.
+        /// `None` removes it. The manager is reset whenever the name changes hands.
+        #[ink(message)]
+        pub fn set_manager(
+            &mut self,
+            name: String,
+            manager: Option<AccountId>,
         ) -> Result<(), Error> {
             let caller = self.env().caller();
-            self.do_set_address(&caller, &name, &loc)
+            self.do_set_manager(&caller, None, &name, manager)
+        }
+
+        /// Allows (or disallows) `operator` to manage all the names of the caller
+        #[ink(message)]
+        pub fn set_operator(&mut self, operator: AccountId, approved: bool) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.do_set_operator(&caller, None, &operator, approved)
         }
 
         /** Setters for NATIVE calls ENDS here */
@@ -93,10 +643,37 @@ mod domain_service {
         /** Setters for XCM calls STARTS here */
 
         /// For xcm-handler use only!
+        /// `paid` is the amount collected by the xc-contract on the origin chain.
+        /// Returns the amount charged, the rest is refunded by the xc-contract.
+        /// The fee split doesn't apply, as the payment is held by the xc-contract.
+        #[ink(message)]
+        pub fn xcm_register_name(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            years: u8,
+            paid: Balance,
+        ) -> Result<Balance, Error> {
+            self.ensure_handler()?;
+            self.do_register_name(&caller, Some(&origin), &name, years, paid)
+        }
+
+        /// For xcm-handler use only!
+        /// `paid` is the amount collected by the xc-contract on the origin chain.
+        /// Returns the amount charged, the rest is refunded by the xc-contract.
+        /// The fee split doesn't apply, as the payment is held by the xc-contract.
         #[ink(message)]
-        pub fn xcm_register_name(&mut self, caller: AccountId, name: String) -> Result<(), Error> {
+        pub fn xcm_renew_name(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            years: u8,
+            paid: Balance,
+        ) -> Result<Balance, Error> {
             self.ensure_handler()?;
-            self.do_register_name(&caller, &name)
+            self.do_renew_name(&caller, Some(&origin), &name, years, paid)
         }
 
         /// For xcm-handler use only!
@@ -104,11 +681,12 @@ mod domain_service {
         pub fn xcm_transfer_name(
             &mut self,
             caller: AccountId,
+            origin: MultilocationEncoded,
             name: String,
             receiver: AccountId,
         ) -> Result<(), Error> {
             self.ensure_handler()?;
-            self.do_transfer_name(&caller, &name, &receiver)
+            self.do_transfer_name(&caller, Some(&origin), &name, &receiver)
         }
 
         /// For xcm-handler use only!
@@ -116,82 +694,1035 @@ mod domain_service {
         pub fn xcm_set_address(
             &mut self,
             caller: AccountId,
+            origin: MultilocationEncoded,
             name: String,
-            loc: MultilocationEncoded,
+            loc: VersionedMultilocationEncoded,
         ) -> Result<(), Error> {
             self.ensure_handler()?;
-            self.do_set_address(&caller, &name, &loc)
+            self.do_set_address(&caller, Some(&origin), &name, &loc)
         }
 
-        /** Setters for XCM calls ENDS here */
-
-        /** Privileged messages STARTS here */
-
-        /// ADMIN use only! Allows updating the xcm-handler address
+        /// For xcm-handler use only!
         #[ink(message)]
-        pub fn set_handler(&mut self, new_handler: AccountId) -> Result<(), Error> {
-            self.ensure_admin()?;
-            self.handler = new_handler;
-            Ok(())
+        pub fn xcm_set_address_for(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            chain: TargetChain,
+            loc: Option<VersionedMultilocationEncoded>,
+        ) -> Result<(), Error> {
+            self.ensure_handler()?;
+            self.do_set_address_for(&caller, Some(&origin), &name, chain, loc)
         }
 
-        /** Privileged messages ENDS here */
-
-        fn do_register_name(&mut self, caller: &AccountId, name: &str) -> Result<(), Error> {
-            if self.name_to_owner.contains(name) {
-                return Err(Error::NameAlreadyExists);
-            }
-            self.name_to_owner.insert(name, caller);
-            Ok(())
+        /// For xcm-handler use only!
+        #[ink(message)]
+        pub fn xcm_set_records(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            records: Vec<(String, Option<String>)>,
+        ) -> Result<(), Error> {
+            self.ensure_handler()?;
+            self.do_set_records(&caller, Some(&origin), &name, records)
         }
 
-        fn do_transfer_name(
+        /// For xcm-handler use only!
+        #[ink(message)]
+        pub fn xcm_set_subdomain_owner(
             &mut self,
-            caller: &AccountId,
-            name: &str,
-            receiver: &AccountId,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            label: String,
+            owner: AccountId,
         ) -> Result<(), Error> {
-            let Some(owner) = self.name_to_owner.get(name) else {
-                return Err(Error::NameDoesNotExists);
-            };
-            if caller != &owner {
-                return Err(Error::NotOwner);
-            }
+            self.ensure_handler()?;
+            self.do_set_subdomain_owner(&caller, Some(&origin), &name, &label, &owner)
+        }
 
-            self.name_to_owner.insert(name, receiver);
-            self.name_to_multilocation.remove(name);
-            Ok(())
+        /// For xcm-handler use only!
+        #[ink(message)]
+        pub fn xcm_revoke_subdomain(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            label: String,
+        ) -> Result<(), Error> {
+            self.ensure_handler()?;
+            self.do_revoke_subdomain(&caller, Some(&origin), &name, &label)
         }
 
-        fn do_set_address(
+        /// For xcm-handler use only!
+        #[ink(message)]
+        pub fn xcm_lock_subdomain(
             &mut self,
-            caller: &AccountId,
-            name: &str,
-            loc: &MultilocationEncoded,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            label: String,
         ) -> Result<(), Error> {
-            let Some(owner) = self.name_to_owner.get(name) else {
-                return Err(Error::NameDoesNotExists);
-            };
-            if caller != &owner {
-                return Err(Error::NotOwner);
-            }
+            self.ensure_handler()?;
+            self.do_lock_subdomain(&caller, Some(&origin), &name, &label)
+        }
 
-            self.name_to_multilocation.insert(name, loc);
-            Ok(())
+        /// For xcm-handler use only!
+        #[ink(message)]
+        /// `location` is the location of the requester relative to this chain,
+        /// which the name must resolve to
+        pub fn xcm_set_primary_name(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            location: MultilocationEncoded,
+            name: Option<String>,
+        ) -> Result<(), Error> {
+            self.ensure_handler()?;
+            self.do_set_primary_name(&caller, Some(&origin), &location, name)
         }
 
-        fn ensure_admin(&self) -> Result<(), Error> {
-            if self.env().caller() != self.admin {
-                Err(Error::NotAdmin)?;
-            }
-            Ok(())
+        /// For xcm-handler use only!
+        #[ink(message)]
+        pub fn xcm_set_manager(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            manager: Option<AccountId>,
+        ) -> Result<(), Error> {
+            self.ensure_handler()?;
+            self.do_set_manager(&caller, Some(&origin), &name, manager)
         }
 
-        fn ensure_handler(&self) -> Result<(), Error> {
+        /// For xcm-handler use only!
+        #[ink(message)]
+        pub fn xcm_set_operator(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            operator: AccountId,
+            approved: bool,
+        ) -> Result<(), Error> {
+            self.ensure_handler()?;
+            self.do_set_operator(&caller, Some(&origin), &operator, approved)
+        }
+
+        /** Setters for XCM calls ENDS here */
+
+        /** Privileged messages STARTS here */
+
+        /// ADMIN use only! Allows updating the xcm-handler address
+        #[ink(message)]
+        pub fn set_handler(&mut self, new_handler: AccountId) -> Result<(), Error> {
+            self.ensure_admin()?;
+            let old_handler = core::mem::replace(&mut self.handler, new_handler);
+
+            self.env().emit_event(HandlerChanged {
+                old_handler,
+                new_handler,
+            });
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating the yearly registration & grace periods (in blocks)
+        #[ink(message)]
+        pub fn set_periods(
+            &mut self,
+            registration_period: BlockNumber,
+            grace_period: BlockNumber,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.registration_period = registration_period;
+            self.grace_period = grace_period;
+
+            self.env().emit_event(PeriodsChanged {
+                registration_period,
+                grace_period,
+            });
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating the yearly price tiers by name length
+        #[ink(message)]
+        pub fn set_price_tiers(&mut self, price_tiers: Vec<Balance>) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if price_tiers.is_empty() {
+                return Err(Error::InvalidPriceConfig);
+            }
+            self.price_tiers = price_tiers;
+            self.emit_price_config_changed();
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating the per-year price multipliers (in percent)
+        #[ink(message)]
+        pub fn set_year_multipliers(&mut self, year_multipliers: Vec<u32>) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if year_multipliers.is_empty() {
+                return Err(Error::InvalidPriceConfig);
+            }
+            self.year_multipliers = year_multipliers;
+            self.emit_price_config_changed();
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating the rules names must comply with.
+        /// Already registered names are not affected.
+        #[ink(message)]
+        pub fn set_name_rules(&mut self, name_rules: NameRules) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.name_rules = name_rules.clone();
+
+            self.env().emit_event(NameRulesChanged { name_rules });
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating how payments for names are shared out.
+        /// The shares must add up to 100.
+        #[ink(message)]
+        pub fn set_fee_split(&mut self, fee_split: FeeSplit) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if !fee_split.is_valid() {
+                return Err(Error::InvalidFeeSplit);
+            }
+            self.fee_split = fee_split;
+
+            self.env().emit_event(FeeSplitChanged { fee_split });
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows withdrawing the collected fees, except for the locked share
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance, to: AccountId) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if amount > self.get_withdrawable_balance() {
+                return Err(Error::InsufficientFunds);
+            }
+            self.env()
+                .transfer(to, amount)
+                .map_err(|_| Error::TransferFailed)?;
+
+            self.env().emit_event(Withdrawn { to, amount });
+            Ok(())
+        }
+
+        /** Privileged messages ENDS here */
+
+        /// Shares out the amount charged for a native payment. The treasury's share,
+        /// along with any overpayment, stays in the contract. If paying the referrer
+        /// fails, its share is kept by the treasury.
+        /// Payments over XCM are held by the xc-contract, so they are not shared out here.
+        fn split_payment(&mut self, amount: Balance, referrer: Option<AccountId>) {
+            let (referrer_share, locked_share) = self.fee_split.shares(amount);
+            self.locked = self.locked.saturating_add(locked_share);
+
+            let Some(referrer) = referrer else {
+                return;
+            };
+            if referrer_share > 0 && self.env().transfer(referrer, referrer_share).is_ok() {
+                self.env().emit_event(ReferralPaid {
+                    referrer,
+                    amount: referrer_share,
+                });
+            }
+        }
+
+        fn emit_price_config_changed(&self) {
+            self.env().emit_event(PriceConfigChanged {
+                price_tiers: self.price_tiers.clone(),
+                year_multipliers: self.year_multipliers.clone(),
+            });
+        }
+
+        fn name_hash(&self, name: &str) -> [u8; 32] {
+            self.env().hash_bytes::<Blake2x256>(name.as_bytes())
+        }
+
+        fn do_register_name(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            name: &str,
+            years: u8,
+            paid: Balance,
+        ) -> Result<Balance, Error> {
+            let name = &self.normalize(name)?;
+            // Subdomains are created by the owner of the parent name
+            if Self::parent_of(name).is_some() {
+                return Err(Error::InvalidName);
+            }
+            if !self.is_reclaimable(name) {
+                return Err(Error::NameAlreadyExists);
+            }
+            let price = self.ensure_payment(name, years, paid)?;
+
+            let expiry = self
+                .env()
+                .block_number()
+                .saturating_add(self.period_for(years));
+
+            // Clear any state left behind by a lapsed registration
+            self.clear_name_data(name);
+            self.clear_subdomains(name);
+
+            self.set_owner(name, Some(caller));
+            self.name_to_expiry.insert(name, &expiry);
+
+            self.env().emit_event(NameRegistered {
+                name_hash: self.name_hash(name),
+                name: name.clone(),
+                owner: *caller,
+                expiry,
+                price,
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(price)
+        }
+
+        fn do_renew_name(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            name: &str,
+            years: u8,
+            paid: Balance,
+        ) -> Result<Balance, Error> {
+            let name = &self.normalize(name)?;
+            let Some(owner) = self.name_to_owner.get(name) else {
+                return Err(Error::NameDoesNotExists);
+            };
+            if self.is_reclaimable(name) {
+                return Err(Error::NameDoesNotExists);
+            }
+            if caller != &owner {
+                return Err(Error::NotOwner);
+            }
+            let price = self.ensure_payment(name, years, paid)?;
+
+            let expiry = self
+                .name_to_expiry
+                .get(name)
+                .unwrap_or_default()
+                .saturating_add(self.period_for(years));
+            self.name_to_expiry.insert(name, &expiry);
+
+            self.env().emit_event(NameRenewed {
+                name_hash: self.name_hash(name),
+                name: name.clone(),
+                expiry,
+                price,
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(price)
+        }
+
+        fn do_transfer_name(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            name: &str,
+            receiver: &AccountId,
+        ) -> Result<(), Error> {
+            let name = &self.normalize(name)?;
+            let owner = self.ensure_manager(caller, name)?;
+            // Would clear the name's data without changing hands
+            if *receiver == owner {
+                return Err(Error::SameOwner);
+            }
+
+            self.set_owner(name, Some(receiver));
+            self.clear_name_data(name);
+
+            self.env().emit_event(NameTransferred {
+                name_hash: self.name_hash(name),
+                name: name.clone(),
+                from: owner,
+                to: *receiver,
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(())
+        }
+
+        fn do_set_address(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            name: &str,
+            loc: &VersionedMultilocationEncoded,
+        ) -> Result<(), Error> {
+            let name = &self.normalize(name)?;
+            self.ensure_manager(caller, name)?;
+            if xcm::v3::MultiLocation::try_from(loc.clone()).is_err() {
+                return Err(Error::InvalidLocation);
+            }
+
+            self.name_to_location.insert(name, loc);
+            self.name_to_multilocation.remove(name);
+
+            self.env().emit_event(AddressSet {
+                name_hash: self.name_hash(name),
+                name: name.clone(),
+                chain: None,
+                address: Some(loc.clone()),
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(())
+        }
+
+        fn do_set_address_for(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            name: &str,
+            chain: TargetChain,
+            loc: Option<VersionedMultilocationEncoded>,
+        ) -> Result<(), Error> {
+            let name = &self.normalize(name)?;
+            self.ensure_manager(caller, name)?;
+
+            let mut chains = self.get_chains(name);
+            chains.retain(|c| c != &chain);
+
+            if let Some(loc) = &loc {
+                if xcm::v3::MultiLocation::try_from(loc.clone()).is_err() {
+                    return Err(Error::InvalidLocation);
+                }
+                if chains.len() >= MAX_ADDRESS_RECORDS {
+                    return Err(Error::TooManyAddressRecords);
+                }
+                self.name_to_chain_address.insert((name, chain), loc);
+                chains.push(chain);
+            } else {
+                self.name_to_chain_address.remove((name, chain));
+            }
+
+            self.name_to_chains.insert(name, &chains);
+
+            self.env().emit_event(AddressSet {
+                name_hash: self.name_hash(name),
+                name: name.clone(),
+                chain: Some(chain),
+                address: loc,
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(())
+        }
+
+        fn do_set_primary_name(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            location: &MultilocationEncoded,
+            name: Option<String>,
+        ) -> Result<(), Error> {
+            let name = name.map(|name| self.normalize(&name)).transpose()?;
+
+            if let Some(name) = &name {
+                self.ensure_owner(caller, name)?;
+                // The primary name must forward-resolve to the caller
+                if !self.resolves_to(name, location) {
+                    return Err(Error::NotResolvingToCaller);
+                }
+                self.account_to_primary_name
+                    .insert(caller, &(name.clone(), *location));
+            } else {
+                self.account_to_primary_name.remove(caller);
+            }
+
+            self.env().emit_event(PrimaryNameSet {
+                account: *caller,
+                name,
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(())
+        }
+
+        fn do_set_manager(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            name: &str,
+            manager: Option<AccountId>,
+        ) -> Result<(), Error> {
+            let name = &self.normalize(name)?;
+            // A manager cannot appoint another manager
+            let owner = self.ensure_owner_or_operator(caller, name)?;
+
+            let manager = manager.filter(|manager| manager != &owner);
+            let (operator, approved) = match manager {
+                Some(manager) => {
+                    self.token_approvals.insert(name, &manager);
+                    (Some(manager), true)
+                }
+                None => (self.token_approvals.take(name), false),
+            };
+
+            if let Some(operator) = operator {
+                self.env().emit_event(Approval {
+                    owner,
+                    operator,
+                    id: Some(Id::Bytes(name.as_bytes().to_vec())),
+                    approved,
+                });
+            }
+
+            self.env().emit_event(ManagerSet {
+                name_hash: self.name_hash(name),
+                name: name.clone(),
+                manager,
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(())
+        }
+
+        fn do_set_operator(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            operator: &AccountId,
+            approved: bool,
+        ) -> Result<(), Error> {
+            if approved {
+                self.operator_approvals.insert((caller, operator), &());
+            } else {
+                self.operator_approvals.remove((caller, operator));
+            }
+
+            self.env().emit_event(Approval {
+                owner: *caller,
+                operator: *operator,
+                id: None,
+                approved,
+            });
+            self.env().emit_event(OperatorSet {
+                owner: *caller,
+                operator: *operator,
+                approved,
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(())
+        }
+
+        fn do_set_subdomain_owner(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            name: &str,
+            label: &str,
+            owner: &AccountId,
+        ) -> Result<(), Error> {
+            let name = &self.normalize(name)?;
+            let label = &self.normalize(label)?;
+            let subdomain = self.ensure_subdomain_access(caller, name, label)?;
+
+            let mut labels = self.name_to_subdomains.get(name).unwrap_or_default();
+            if !labels.iter().any(|l| l == label) {
+                if labels.len() >= MAX_SUBDOMAINS {
+                    return Err(Error::TooManySubdomains);
+                }
+                labels.push(label.into());
+                self.name_to_subdomains.insert(name, &labels);
+            } else if self.name_to_owner.get(&subdomain).as_ref() != Some(owner) {
+                // Same as a transfer, the new owner starts afresh
+                self.clear_name_data(&subdomain);
+            }
+
+            self.set_owner(&subdomain, Some(owner));
+
+            self.env().emit_event(SubdomainOwnerSet {
+                name_hash: self.name_hash(&subdomain),
+                name: subdomain,
+                owner: *owner,
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(())
+        }
+
+        fn do_revoke_subdomain(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            name: &str,
+            label: &str,
+        ) -> Result<(), Error> {
+            let name = &self.normalize(name)?;
+            let label = &self.normalize(label)?;
+            let subdomain = self.ensure_subdomain_access(caller, name, label)?;
+
+            let mut labels = self.name_to_subdomains.get(name).unwrap_or_default();
+            let Some(idx) = labels.iter().position(|l| l == label) else {
+                return Err(Error::NameDoesNotExists);
+            };
+            labels.remove(idx);
+            self.name_to_subdomains.insert(name, &labels);
+
+            self.set_owner(&subdomain, None);
+            self.clear_name_data(&subdomain);
+
+            self.env().emit_event(SubdomainRevoked {
+                name_hash: self.name_hash(&subdomain),
+                name: subdomain,
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(())
+        }
+
+        fn do_lock_subdomain(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            name: &str,
+            label: &str,
+        ) -> Result<(), Error> {
+            let name = &self.normalize(name)?;
+            let label = &self.normalize(label)?;
+            let subdomain = self.ensure_subdomain_access(caller, name, label)?;
+            if !self.name_to_owner.contains(&subdomain) {
+                return Err(Error::NameDoesNotExists);
+            }
+
+            self.locked_subdomains.insert(&subdomain, &());
+
+            self.env().emit_event(SubdomainLocked {
+                name_hash: self.name_hash(&subdomain),
+                name: subdomain,
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(())
+        }
+
+        /// Checks the caller controls the subdomain `label.name` and returns its full name
+        fn ensure_subdomain_access(
+            &self,
+            caller: &AccountId,
+            name: &str,
+            label: &str,
+        ) -> Result<String, Error> {
+            // Only a single level of subdomains is supported
+            if Self::parent_of(name).is_some() || label.is_empty() || label.contains('.') {
+                return Err(Error::InvalidName);
+            }
+            self.ensure_owner(caller, name)?;
+
+            let subdomain = format!("{label}.{name}");
+            if self.locked_subdomains.contains(&subdomain) {
+                return Err(Error::SubdomainLocked);
+            }
+            Ok(subdomain)
+        }
+
+        /// Removes all the subdomains of the name, including the locked ones
+        fn clear_subdomains(&mut self, name: &str) {
+            for label in self.name_to_subdomains.get(name).unwrap_or_default() {
+                let subdomain = format!("{label}.{name}");
+                self.set_owner(&subdomain, None);
+                self.locked_subdomains.remove(&subdomain);
+                self.clear_name_data(&subdomain);
+            }
+            self.name_to_subdomains.remove(name);
+        }
+
+        /// Updates the owner of the name along with the PSP34 token bookkeeping.
+        /// `None` removes the name altogether.
+        fn set_owner(&mut self, name: &str, owner: Option<&AccountId>) {
+            // Approvals never survive a change of ownership
+            self.token_approvals.remove(name);
+
+            let previous = self.name_to_owner.get(name);
+            if previous.as_ref() == owner {
+                return;
+            }
+
+            if let Some(previous) = previous {
+                self.remove_owned_name(&previous, name);
+            }
+            self.env().emit_event(Transfer {
+                from: previous,
+                to: owner.copied(),
+                id: Id::Bytes(name.as_bytes().to_vec()),
+            });
+
+            let Some(owner) = owner else {
+                self.name_to_owner.remove(name);
+                self.remove_token(name);
+                return;
+            };
+
+            let count = self.owner_name_count.get(owner).unwrap_or_default();
+            self.owner_to_names
+                .insert((owner, count), &String::from(name));
+            self.name_to_owner_index.insert(name, &count);
+            self.owner_name_count.insert(owner, &(count + 1));
+            self.name_to_owner.insert(name, owner);
+
+            if !self.name_to_token_index.contains(name) {
+                self.token_index
+                    .insert(self.total_tokens, &String::from(name));
+                self.name_to_token_index.insert(name, &self.total_tokens);
+                self.total_tokens += 1;
+            }
+        }
+
+        /// Removes the name from the names held by the owner, moving the last one in its place
+        fn remove_owned_name(&mut self, owner: &AccountId, name: &str) {
+            let Some(idx) = self.name_to_owner_index.get(name) else {
+                return;
+            };
+            self.name_to_owner_index.remove(name);
+
+            let last = self.owner_name_count.get(owner).unwrap_or_default() - 1;
+            if idx != last {
+                let last_name = self.owner_to_names.get((owner, last)).unwrap_or_default();
+                self.owner_to_names.insert((owner, idx), &last_name);
+                self.name_to_owner_index.insert(&last_name, &idx);
+            }
+            self.owner_to_names.remove((owner, last));
+            if last == 0 {
+                self.owner_name_count.remove(owner);
+            } else {
+                self.owner_name_count.insert(owner, &last);
+            }
+        }
+
+        /// Removes the name from the token enumeration, moving the last token in its place
+        fn remove_token(&mut self, name: &str) {
+            let Some(idx) = self.name_to_token_index.get(name) else {
+                return;
+            };
+            self.name_to_token_index.remove(name);
+
+            let last = self.total_tokens - 1;
+            if idx != last {
+                let last_name = self.token_index.get(last).unwrap_or_default();
+                self.token_index.insert(idx, &last_name);
+                self.name_to_token_index.insert(&last_name, &idx);
+            }
+            self.token_index.remove(last);
+            self.total_tokens = last;
+        }
+
+        /// Returns the name identified by the PSP34 token id.
+        /// Ids must hold the normalized name, so that a name has a single token id.
+        fn token_name(id: &Id) -> Option<String> {
+            let Id::Bytes(bytes) = id else {
+                return None;
+            };
+            let name = String::from_utf8(bytes.clone()).ok()?;
+            (normalize_name(&name) == name).then_some(name)
+        }
+
+        /// Returns the parent name of a subdomain, e.g. `alice` for `pay.alice`
+        fn parent_of(name: &str) -> Option<&str> {
+            name.split_once('.').map(|(_, parent)| parent)
+        }
+
+        fn do_set_records(
+            &mut self,
+            caller: &AccountId,
+            origin: Option<&MultilocationEncoded>,
+            name: &str,
+            updates: Vec<(String, Option<String>)>,
+        ) -> Result<(), Error> {
+            let name = &self.normalize(name)?;
+            self.ensure_owner(caller, name)?;
+
+            let mut records = self.name_to_records.get(name).unwrap_or_default();
+            let mut keys = Vec::new();
+            for (key, value) in updates {
+                records.retain(|(k, _)| k != &key);
+                keys.push(key.clone());
+
+                let Some(value) = value else {
+                    continue;
+                };
+                if key.len() > MAX_RECORD_KEY_LENGTH || value.len() > MAX_RECORD_VALUE_LENGTH {
+                    return Err(Error::RecordTooLong);
+                }
+                records.push((key, value));
+            }
+
+            if records.len() > MAX_RECORDS {
+                return Err(Error::TooManyRecords);
+            }
+
+            if records.is_empty() {
+                self.name_to_records.remove(name);
+            } else {
+                self.name_to_records.insert(name, &records);
+            }
+
+            self.env().emit_event(RecordsUpdated {
+                name_hash: self.name_hash(name),
+                name: name.clone(),
+                keys,
+                via_xcm: origin.is_some(),
+                origin: origin.copied(),
+            });
+            Ok(())
+        }
+
+        fn get_chains(&self, name: &str) -> Vec<TargetChain> {
+            self.name_to_chains.get(name).unwrap_or_default()
+        }
+
+        /// Returns true if the resolving address of the name is the given location
+        fn resolves_to(&self, name: &str, location: &MultilocationEncoded) -> bool {
+            let expected: xcm::v3::MultiLocation = (*location).into();
+            self.get_location(name)
+                .and_then(|loc| xcm::v3::MultiLocation::try_from(loc).ok())
+                == Some(expected)
+        }
+
+        fn get_location(&self, name: &str) -> Option<VersionedMultilocationEncoded> {
+            self.name_to_location
+                .get(name)
+                .or_else(|| self.name_to_multilocation.get(name).map(Into::into))
+        }
+
+        fn clear_name_data(&mut self, name: &str) {
+            self.name_to_location.remove(name);
+            self.name_to_multilocation.remove(name);
+
+            for chain in self.get_chains(name) {
+                self.name_to_chain_address.remove((name, chain));
+            }
+            self.name_to_chains.remove(name);
+            self.name_to_records.remove(name);
+        }
+
+        /// Normalizes the name and checks it against the naming rules
+        fn normalize(&self, name: &str) -> Result<String, Error> {
+            let name = normalize_name(name);
+            if !self.name_rules.is_valid(&name) {
+                return Err(Error::InvalidName);
+            }
+            Ok(name)
+        }
+
+        /// Returns the price to be charged if `paid` covers it
+        fn ensure_payment(&self, name: &str, years: u8, paid: Balance) -> Result<Balance, Error> {
+            if years == 0 {
+                return Err(Error::InvalidDuration);
+            }
+            let price = self.get_price(name.into(), years);
+            if paid < price {
+                return Err(Error::PaymentNotReceived);
+            }
+            Ok(price)
+        }
+
+        fn period_for(&self, years: u8) -> BlockNumber {
+            self.registration_period
+                .saturating_mul(years as BlockNumber)
+        }
+
+        /// Returns true if the name is registered and not yet expired.
+        /// A subdomain is active as long as it exists and its parent is active.
+        fn is_active(&self, name: &str) -> bool {
+            if let Some(parent) = Self::parent_of(name) {
+                return self.name_to_owner.contains(name) && self.is_active(parent);
+            }
+            self.name_to_expiry
+                .get(name)
+                .is_some_and(|expiry| self.env().block_number() < expiry)
+        }
+
+        /// Returns true if the name is free to be registered by anyone,
+        /// i.e. it was never registered or its grace period has elapsed
+        fn is_reclaimable(&self, name: &str) -> bool {
+            let Some(expiry) = self.name_to_expiry.get(name) else {
+                return true;
+            };
+            self.env().block_number() >= expiry.saturating_add(self.grace_period)
+        }
+
+        fn ensure_owner(&self, caller: &AccountId, name: &str) -> Result<(), Error> {
+            let Some(owner) = self.name_to_owner.get(name) else {
+                return Err(Error::NameDoesNotExists);
+            };
+            if !self.is_active(name) {
+                return Err(Error::NameExpired);
+            }
+            if caller != &owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+
+        /// Checks the caller is the owner of the name or an operator of the owner.
+        /// Returns the owner.
+        fn ensure_owner_or_operator(
+            &self,
+            caller: &AccountId,
+            name: &str,
+        ) -> Result<AccountId, Error> {
+            let Some(owner) = self.name_to_owner.get(name) else {
+                return Err(Error::NameDoesNotExists);
+            };
+            if !self.is_active(name) {
+                return Err(Error::NameExpired);
+            }
+            if caller != &owner && !self.operator_approvals.contains((owner, caller)) {
+                return Err(Error::NotOwner);
+            }
+            Ok(owner)
+        }
+
+        /// Checks the caller is the owner of the name, its manager or an operator of the owner.
+        /// Returns the owner.
+        fn ensure_manager(&self, caller: &AccountId, name: &str) -> Result<AccountId, Error> {
+            match self.ensure_owner_or_operator(caller, name) {
+                Err(Error::NotOwner) if self.token_approvals.get(name).as_ref() == Some(caller) => {
+                    self.name_to_owner.get(name).ok_or(Error::NameDoesNotExists)
+                }
+                rs => rs,
+            }
+        }
+
+        fn ensure_admin(&self) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                Err(Error::NotAdmin)?;
+            }
+            Ok(())
+        }
+
+        fn ensure_handler(&self) -> Result<(), Error> {
             if self.env().caller() != self.handler {
                 Err(Error::NotHandler)?;
             }
             Ok(())
         }
     }
+
+    /// Names are PSP34 tokens identified by `Id::Bytes(name)`.
+    /// Expired names are not owned by anyone, yet they remain listed by
+    /// `balance_of` and the enumerable extension until reclaimed.
+    impl PSP34 for DomainService {
+        #[ink(message)]
+        fn collection_id(&self) -> Id {
+            Id::Bytes(self.env().account_id().as_ref().to_vec())
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> u32 {
+            self.owner_name_count.get(owner).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        fn owner_of(&self, id: Id) -> Option<AccountId> {
+            self.get_owner(Self::token_name(&id)?)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool {
+            if self.operator_approvals.contains((owner, operator)) {
+                return true;
+            }
+            let Some(name) = id.as_ref().and_then(Self::token_name) else {
+                return false;
+            };
+            self.name_to_owner.get(&name) == Some(owner)
+                && self.token_approvals.get(&name) == Some(operator)
+        }
+
+        /// Approving a single name requires the caller to own it or be an operator of its owner
+        #[ink(message)]
+        fn approve(
+            &mut self,
+            operator: AccountId,
+            id: Option<Id>,
+            approved: bool,
+        ) -> Result<(), PSP34Error> {
+            let caller = self.env().caller();
+            if operator == caller {
+                return Err(PSP34Error::SelfApprove);
+            }
+
+            let Some(id) = id else {
+                return self
+                    .do_set_operator(&caller, None, &operator, approved)
+                    .map_err(|e| PSP34Error::Custom(format!("{e:?}")));
+            };
+
+            let name = Self::token_name(&id).ok_or(PSP34Error::TokenNotExists)?;
+            let owner = self
+                .get_owner(name.clone())
+                .ok_or(PSP34Error::TokenNotExists)?;
+            if caller != owner && !self.operator_approvals.contains((owner, caller)) {
+                return Err(PSP34Error::NotApproved);
+            }
+
+            self.do_set_manager(&caller, None, &name, approved.then_some(operator))
+                .map_err(|e| PSP34Error::Custom(format!("{e:?}")))
+        }
+
+        /// Same as `transfer_name`: the resolving address and records of the name are cleared
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, id: Id, _data: Vec<u8>) -> Result<(), PSP34Error> {
+            let caller = self.env().caller();
+            let name = Self::token_name(&id).ok_or(PSP34Error::TokenNotExists)?;
+            let owner = self
+                .get_owner(name.clone())
+                .ok_or(PSP34Error::TokenNotExists)?;
+            if caller != owner && !self.allowance(owner, caller, Some(id)) {
+                return Err(PSP34Error::NotApproved);
+            }
+
+            self.do_transfer_name(&owner, None, &name, &to)
+                .map_err(|e| PSP34Error::Custom(format!("{e:?}")))
+        }
+
+        #[ink(message)]
+        fn total_supply(&self) -> u128 {
+            self.total_tokens
+        }
+    }
+
+    impl PSP34Metadata for DomainService {
+        /// The `name` attribute returns the name itself, any other key returns
+        /// the text record of the name
+        #[ink(message)]
+        fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>> {
+            let name = Self::token_name(&id)?;
+            let key = String::from_utf8(key).ok()?;
+            if key == "name" {
+                return self.is_active(&name).then(|| name.into_bytes());
+            }
+            self.get_record(name, key).map(String::into_bytes)
+        }
+    }
+
+    impl PSP34Enumerable for DomainService {
+        #[ink(message)]
+        fn owners_token_by_index(&self, owner: AccountId, index: u128) -> Result<Id, PSP34Error> {
+            let name = u32::try_from(index)
+                .ok()
+                .and_then(|idx| self.owner_to_names.get((owner, idx)))
+                .ok_or(PSP34Error::TokenNotExists)?;
+            Ok(Id::Bytes(name.into_bytes()))
+        }
+
+        #[ink(message)]
+        fn token_by_index(&self, index: u128) -> Result<Id, PSP34Error> {
+            let name = self
+                .token_index
+                .get(index)
+                .ok_or(PSP34Error::TokenNotExists)?;
+            Ok(Id::Bytes(name.into_bytes()))
+        }
+    }
 }
@@ -0,0 +1,88 @@
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// PSP34 token id. Names are identified by `Id::Bytes(name)`.
+#[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Id {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP34Error {
+    /// Custom error type for cases if writer of traits added own restrictions
+    Custom(String),
+    /// Returned if owner approves self
+    SelfApprove,
+    /// Returned if the caller doesn't have allowance for transferring
+    NotApproved,
+    /// Returned if the owner already own the token
+    TokenExists,
+    /// Returned if the token doesn't exist
+    TokenNotExists,
+    /// Returned if safe transfer check fails
+    SafeTransferCheckFailed(String),
+}
+
+#[ink::trait_definition]
+pub trait PSP34 {
+    /// Returns the collection `Id` of the NFT token
+    #[ink(message)]
+    fn collection_id(&self) -> Id;
+
+    /// Returns the number of tokens owned by `owner`
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u32;
+
+    /// Returns the owner of the token, if any
+    #[ink(message)]
+    fn owner_of(&self, id: Id) -> Option<AccountId>;
+
+    /// Returns true if `operator` is approved by `owner` to transfer the token `id`,
+    /// or all of the owner's tokens if `id` is `None`
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
+
+    /// Approves (or disapproves) `operator` to transfer the token `id` of the caller,
+    /// or all of the caller's tokens if `id` is `None`
+    #[ink(message)]
+    fn approve(
+        &mut self,
+        operator: AccountId,
+        id: Option<Id>,
+        approved: bool,
+    ) -> Result<(), PSP34Error>;
+
+    /// Transfers the token `id` to `to`
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error>;
+
+    /// Returns the number of tokens
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+}
+
+#[ink::trait_definition]
+pub trait PSP34Metadata {
+    /// Returns the attribute of the token `id` for the given `key`
+    #[ink(message)]
+    fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>>;
+}
+
+#[ink::trait_definition]
+pub trait PSP34Enumerable {
+    /// Returns the token of `owner` at `index`
+    #[ink(message)]
+    fn owners_token_by_index(&self, owner: AccountId, index: u128) -> Result<Id, PSP34Error>;
+
+    /// Returns the token at `index`
+    #[ink(message)]
+    fn token_by_index(&self, index: u128) -> Result<Id, PSP34Error>;
+}
@@ -0,0 +1,36 @@
+use xcm::v3::prelude::*;
+
+/// Universal location of a chain within the relay-chain network:
+/// the relay chain itself (`para_id: None`) or one of its parachains.
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ChainLocation {
+    pub para_id: Option<u32>,
+}
+
+impl ChainLocation {
+    pub fn new(para_id: Option<u32>) -> Self {
+        Self { para_id }
+    }
+
+    /// Interior of the chain's location w.r.t the relay chain, used as reanchoring context
+    pub fn interior(&self) -> InteriorMultiLocation {
+        match self.para_id {
+            Some(id) => X1(Parachain(id)),
+            None => Here,
+        }
+    }
+
+    /// Path to this chain as seen from the chain at `here`
+    pub fn path_from(&self, here: &ChainLocation) -> MultiLocation {
+        if self == here {
+            return MultiLocation::here();
+        }
+
+        let parents = here.interior().len() as u8;
+        MultiLocation::new(parents, self.interior())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{ContractsCall, RuntimeCall, XcmExecute, XcmReserveTransfer, XcmSend};
+
+/// Pallet & call indices used to encode runtime calls for a chain.
+/// Make sure indexes are valid for the chain in use!
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ChainProfile {
+    pub contracts_pallet: u8,
+    pub contracts_call: u8,
+    pub xcm_pallet: u8,
+    pub xcm_send: u8,
+    /// Call index of `pallet_xcm::limited_reserve_transfer_assets`
+    pub xcm_reserve_transfer: u8,
+    /// Call index of `pallet_xcm::execute`
+    pub xcm_execute: u8,
+}
+
+impl Default for ChainProfile {
+    fn default() -> Self {
+        Self {
+            contracts_pallet: 40,
+            contracts_call: 6,
+            xcm_pallet: 31,
+            xcm_send: 0,
+            xcm_reserve_transfer: 8,
+            xcm_execute: 3,
+        }
+    }
+}
+
+impl ChainProfile {
+    pub fn contracts_call(&self, args: ContractsCall) -> RuntimeCall<ContractsCall> {
+        RuntimeCall {
+            pallet_index: self.contracts_pallet,
+            call_index: self.contracts_call,
+            args,
+        }
+    }
+
+    pub fn xcm_send(&self, args: XcmSend) -> RuntimeCall<XcmSend> {
+        RuntimeCall {
+            pallet_index: self.xcm_pallet,
+            call_index: self.xcm_send,
+            args,
+        }
+    }
+
+    pub fn xcm_reserve_transfer(
+        &self,
+        args: XcmReserveTransfer,
+    ) -> RuntimeCall<XcmReserveTransfer> {
+        RuntimeCall {
+            pallet_index: self.xcm_pallet,
+            call_index: self.xcm_reserve_transfer,
+            args,
+        }
+    }
+
+    pub fn xcm_execute(&self, args: XcmExecute) -> RuntimeCall<XcmExecute> {
+        RuntimeCall {
+            pallet_index: self.xcm_pallet,
+            call_index: self.xcm_execute,
+            args,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+/// Errors returned by `domain_service`.
+/// Shared with the xcm-handler & xc-contracts so that they can be relayed over XCM.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum DomainServiceError {
+    NotAdmin,
+    NotHandler,
+    NotOwner,
+    NameAlreadyExists,
+    NameDoesNotExists,
+    PaymentNotReceived,
+    NameExpired,
+    InvalidDuration,
+    InvalidPriceConfig,
+    InvalidLocation,
+    TooManyAddressRecords,
+    TooManyRecords,
+    RecordTooLong,
+    InvalidName,
+    SubdomainLocked,
+    TooManySubdomains,
+    InvalidFeeSplit,
+    InsufficientFunds,
+    TransferFailed,
+    NotResolvingToCaller,
+    SameOwner,
+}
+
+/// Errors returned by `xcm_handler`.
+/// The outcome of every write operation is reported back to the xc-contract in this format.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum HandlerError {
+    NotAdmin,
+    UnknownCaller,
+    CallRuntimeFailed,
+    InkEnvError,
+    DomainService(DomainServiceError),
+    UnsupportedXcmVersion,
+    UnsupportedMultiLocationFormat,
+    ReanchoringFailed,
+    InvalidBatchSize,
+}
+
+impl From<ink::env::Error> for HandlerError {
+    fn from(e: ink::env::Error) -> Self {
+        match e {
+            ink::env::Error::CallRuntimeFailed => HandlerError::CallRuntimeFailed,
+            _ => HandlerError::InkEnvError,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::Weight;
+use sp_weights::constants::{WEIGHT_PROOF_SIZE_PER_MB, WEIGHT_REF_TIME_PER_SECOND};
+
+/// Weight & fee parameters of a destination chain, used to estimate the cost of executing a message.
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct FeeEstimator {
+    /// Weight (ref-time, proof-size) charged per XCM instruction
+    pub weight_per_instruction: (u64, u64),
+    /// Units of the fee asset charged per second of ref-time
+    pub units_per_second: u128,
+    /// Units of the fee asset charged per MB of proof-size
+    pub units_per_mb: u128,
+}
+
+impl FeeEstimator {
+    /// Weight of a message with `number_of_instructions`, `Transact` included with `transact_weight`
+    pub fn estimate_weight(&self, number_of_instructions: u64, transact_weight: Weight) -> Weight {
+        let (ref_time, proof_size) = self.weight_per_instruction;
+        Weight::from_parts(ref_time, proof_size)
+            .saturating_mul(number_of_instructions)
+            .saturating_add(transact_weight)
+    }
+
+    pub fn estimate_fee_for_weight(&self, weight: Weight) -> u128 {
+        let ref_time_fee = self
+            .units_per_second
+            .saturating_mul(weight.ref_time() as u128)
+            / (WEIGHT_REF_TIME_PER_SECOND as u128);
+        let proof_size_fee = self
+            .units_per_mb
+            .saturating_mul(weight.proof_size() as u128)
+            / (WEIGHT_PROOF_SIZE_PER_MB as u128);
+
+        ref_time_fee.saturating_add(proof_size_fee)
+    }
+
+    pub fn estimate_message_fee(
+        &self,
+        number_of_instructions: u64,
+        transact_weight: Weight,
+    ) -> u128 {
+        let weight = self.estimate_weight(number_of_instructions, transact_weight);
+        self.estimate_fee_for_weight(weight)
+    }
+}
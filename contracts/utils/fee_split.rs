@@ -0,0 +1,37 @@
+/// How payments for names are shared out, in percent of the amount charged.
+/// The shares must add up to 100.
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct FeeSplit {
+    /// Share kept by the contract, to be withdrawn by the admin
+    pub treasury: u8,
+    /// Share paid out to the referrer of the registration. Kept by the treasury if none.
+    pub referrer: u8,
+    /// Share locked in the contract for good
+    pub locked: u8,
+}
+
+impl Default for FeeSplit {
+    fn default() -> Self {
+        Self {
+            treasury: 100,
+            referrer: 0,
+            locked: 0,
+        }
+    }
+}
+
+impl FeeSplit {
+    pub fn is_valid(&self) -> bool {
+        self.treasury as u16 + self.referrer as u16 + self.locked as u16 == 100
+    }
+
+    /// Returns the (referrer, locked) shares of the amount, the rest goes to the treasury
+    pub fn shares(&self, amount: u128) -> (u128, u128) {
+        let share = |percent: u8| amount.saturating_mul(percent as u128) / 100;
+        (share(self.referrer), share(self.locked))
+    }
+}
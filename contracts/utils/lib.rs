@@ -1,47 +1,85 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+mod chain_location;
+mod chain_profile;
+mod errors;
+mod fee_estimator;
+mod fee_split;
 mod multilocation_encoded;
+mod name_rules;
+mod operation;
+mod target_chain;
+mod xcm_fee;
 
 pub use sp_weights::Weight;
-pub use multilocation_encoded::MultilocationEncoded;
+pub use chain_location::ChainLocation;
+pub use chain_profile::ChainProfile;
+pub use errors::{DomainServiceError, HandlerError};
+pub use fee_estimator::FeeEstimator;
+pub use fee_split::FeeSplit;
+pub use multilocation_encoded::{
+    JunctionEncoded, MultilocationEncoded, MultilocationEncodedV2, NetworkIdEncoded,
+    VersionedMultilocationEncoded,
+};
+pub use name_rules::{normalize_name, NameRules};
+pub use operation::Operation;
+pub use target_chain::TargetChain;
+pub use xcm_fee::{XcmFee, XcmFeeMode};
 use ink::prelude::boxed::Box;
 use ink::prelude::{vec, vec::Vec};
 use ink::primitives::AccountId;
 use xcm::v3::prelude::*;
 
-use sp_weights::constants::{WEIGHT_REF_TIME_PER_SECOND, WEIGHT_PROOF_SIZE_PER_MB};
+/// Gas limit of the contract call, unless specified otherwise
+pub const DEFAULT_GAS_LIMIT: Weight = Weight::from_parts(10_000_000_000, 150_000);
+/// Weight reserved for `Transact` on top of the contract call's gas limit
+pub const ADDITIONAL_GAS: Weight = Weight::from_parts(1_000_000_000, 10_000);
 
-const WEIGHT_PER_INSTRUCTION: Weight = Weight::from_parts(1, 1);
-const UNITS_PER_SECOND: u128 = 1;
-const UNITS_PER_MB: u128 = 1;
+/// Maximum number of names that can be resolved in a single batch request
+pub const MAX_BATCH_SIZE: usize = 20;
 
-pub fn estimate_message_fee(number_of_instructions: u64) -> u128 {
-    let weight = estimate_weight(number_of_instructions);
-    estimate_fee_for_weight(weight)
+/// Weight reserved for the `Transact` of a contract call with given gas limit
+pub fn transact_weight(gas_limit: Option<Weight>) -> Weight {
+    gas_limit
+        .unwrap_or(DEFAULT_GAS_LIMIT)
+        .saturating_add(ADDITIONAL_GAS)
 }
 
-pub fn estimate_weight(number_of_instructions: u64) -> Weight {
-    WEIGHT_PER_INSTRUCTION.saturating_mul(number_of_instructions)
+/// Number of instructions executed on the destination for a contract call,
+/// including the `DescendOrigin` prepended by `pallet_xcm::send`
+pub fn instruction_count(paid: bool) -> u64 {
+    if paid {
+        6
+    } else {
+        3
+    }
 }
 
-pub fn estimate_fee_for_weight(weight: Weight) -> u128 {
-    UNITS_PER_SECOND * (weight.ref_time() as u128) / (WEIGHT_REF_TIME_PER_SECOND as u128)
-        + UNITS_PER_MB * (weight.proof_size() as u128) / (WEIGHT_PROOF_SIZE_PER_MB as u128)
+/// Destination chain of an XCM contract call
+pub struct Destination {
+    /// Path to the chain, as seen from here
+    pub path: MultiLocation,
+    /// Runtime call indices of the chain
+    pub profile: ChainProfile,
+    /// Fee paid for the execution, `None` for unpaid execution
+    pub fee: Option<XcmFee>,
 }
 
+/// Sends an XCM to call the contract on the destination chain.
+/// `local` profile encodes the `pallet_xcm::send` call on this chain, while
+/// the destination's profile encodes the `pallet_contracts::call` on it.
 pub fn make_xcm_contract_call<C: ink::env::ContractEnv>(
-    path_to_chain: xcm::VersionedMultiLocation,
+    local: &ChainProfile,
+    destination: Destination,
     contract_address: AccountId,
     payload: Vec<u8>,
     value: u128,
     gas_limit: Option<Weight>,
 ) -> Result<(), ink::env::Error> {
-    let gas_limit = gas_limit.unwrap_or(Weight::from_parts(10_000_000_000, 150_000));
-    let additional_gas = Weight::from_parts(1_000_000_000, 10_000);
-    // let est_wt = estimate_weight(4) + gas_limit * 2;
-    // let fee = estimate_fee_for_weight(est_wt);
+    let require_weight_at_most = transact_weight(gas_limit);
+    let gas_limit = gas_limit.unwrap_or(DEFAULT_GAS_LIMIT);
 
-    let contract_call = RuntimeCall::Contracts(ContractsCall::Call {
+    let contract_call = destination.profile.contracts_call(ContractsCall {
         dest: contract_address.into(),
         value,
         gas_limit,
@@ -49,58 +87,184 @@ pub fn make_xcm_contract_call<C: ink::env::ContractEnv>(
         data: payload,
     });
 
+    let transact = Transact {
+        origin_kind: OriginKind::SovereignAccount,
+        require_weight_at_most,
+        call: scale::Encode::encode(&contract_call).into(),
+    };
+
+    let message: Xcm<()> = match destination.fee {
+        Some(XcmFee { asset, refund_to }) => Xcm(vec![
+            WithdrawAsset(asset.clone().into()),
+            BuyExecution {
+                fees: asset,
+                weight_limit: WeightLimit::Unlimited,
+            },
+            transact,
+            RefundSurplus,
+            DepositAsset {
+                assets: Wild(AllCounted(1)),
+                beneficiary: refund_to,
+            },
+        ]),
+        None => Xcm(vec![
+            UnpaidExecution {
+                weight_limit: WeightLimit::Unlimited,
+                check_origin: None,
+            },
+            transact,
+        ]),
+    };
+
+    let xcm_call = local.xcm_send(XcmSend {
+        dest: Box::new(destination.path.into()),
+        message: Box::new(xcm::VersionedXcm::V3(message)),
+    });
+
+    ink::env::call_runtime::<C::Env, _>(&xcm_call)
+}
+
+/// Reserve-transfers `amount` of the native asset of the `asset` chain from the contract's
+/// account to `beneficiary` on the `destination` chain.
+/// The destination pays for the execution of the deposit out of the transferred amount.
+pub fn make_reserve_transfer<C: ink::env::ContractEnv>(
+    local: &ChainProfile,
+    here: &ChainLocation,
+    destination: &ChainLocation,
+    asset: &ChainLocation,
+    amount: u128,
+    beneficiary: AccountId,
+) -> Result<(), ink::env::Error> {
+    let beneficiary: MultiLocation = AccountId32 {
+        network: None,
+        id: *beneficiary.as_ref(),
+    }
+    .into();
+    let asset: MultiAsset = (asset.path_from(here), amount).into();
+
+    let xcm_call = local.xcm_reserve_transfer(XcmReserveTransfer {
+        dest: Box::new(destination.path_from(here).into()),
+        beneficiary: Box::new(beneficiary.into()),
+        assets: Box::new(MultiAssets::from(asset).into()),
+        fee_asset_item: 0,
+        weight_limit: WeightLimit::Unlimited,
+    });
+
+    ink::env::call_runtime::<C::Env, _>(&xcm_call)
+}
+
+/// Transfers `amount` of the native asset of the `reserve` chain, other than this chain, from
+/// the contract's account to `beneficiary` on the `destination` chain. The asset is withdrawn
+/// here & on the reserve, which deposits it on the destination unless it is the destination.
+/// Each chain pays for the execution out of the transferred amount.
+/// The local execution is bounded by `instruction_weight` per instruction of the message.
+pub fn make_reserve_withdraw<C: ink::env::ContractEnv>(
+    local: &ChainProfile,
+    here: &ChainLocation,
+    destination: &ChainLocation,
+    reserve: &ChainLocation,
+    amount: u128,
+    beneficiary: AccountId,
+    instruction_weight: Weight,
+) -> Result<(), ink::env::Error> {
+    let beneficiary: MultiLocation = AccountId32 {
+        network: None,
+        id: *beneficiary.as_ref(),
+    }
+    .into();
+    let deposit = DepositAsset {
+        assets: Wild(AllCounted(1)),
+        beneficiary,
+    };
+
+    let on_reserve = if reserve == destination {
+        Xcm(vec![
+            BuyExecution {
+                fees: (Here, amount).into(),
+                weight_limit: WeightLimit::Unlimited,
+            },
+            deposit,
+        ])
+    } else {
+        // Both the reserve & the destination buy execution, each with up to half of the amount
+        let fees = amount / 2;
+        Xcm(vec![
+            BuyExecution {
+                fees: (Here, fees).into(),
+                weight_limit: WeightLimit::Unlimited,
+            },
+            DepositReserveAsset {
+                assets: Wild(AllCounted(1)),
+                dest: destination.path_from(reserve),
+                xcm: Xcm(vec![
+                    BuyExecution {
+                        fees: (reserve.path_from(destination), fees).into(),
+                        weight_limit: WeightLimit::Unlimited,
+                    },
+                    deposit,
+                ]),
+            },
+        ])
+    };
+
+    let asset: MultiAsset = (reserve.path_from(here), amount).into();
     let message: Xcm<()> = Xcm(vec![
-        // WithdrawAsset(vec![(Parent, fee).into()].into()),
-        // BuyExecution {
-        //     fees: (Parent, fee).into(),
-        //     weight_limit: WeightLimit::Unlimited,
-        // },
-        UnpaidExecution {
-            weight_limit: WeightLimit::Unlimited,
-            check_origin: None,  
-        },
-        Transact {
-            origin_kind: OriginKind::SovereignAccount,
-            require_weight_at_most: gas_limit + additional_gas,
-            call: scale::Encode::encode(&contract_call).into(),
+        WithdrawAsset(asset.into()),
+        InitiateReserveWithdraw {
+            assets: Wild(AllCounted(1)),
+            reserve: reserve.path_from(here),
+            xcm: on_reserve,
         },
     ]);
+    let max_weight = instruction_weight.saturating_mul(message.len() as u64);
 
-    let xcm_call = RuntimeCall::Xcm(XcmCall::Send {
-        dest: Box::new(path_to_chain),
+    let xcm_call = local.xcm_execute(XcmExecute {
         message: Box::new(xcm::VersionedXcm::V3(message)),
+        max_weight,
     });
 
     ink::env::call_runtime::<C::Env, _>(&xcm_call)
 }
 
-// @dev Make sure indexes are valid for chain in use!
+/// Runtime call encoded with the pallet & call indices of the target chain (see `ChainProfile`)
+#[derive(scale::Encode)]
+pub struct RuntimeCall<Args> {
+    pub pallet_index: u8,
+    pub call_index: u8,
+    pub args: Args,
+}
+
+/// Arguments of `pallet_xcm::send`
+#[derive(scale::Encode)]
+pub struct XcmSend {
+    pub dest: Box<xcm::VersionedMultiLocation>,
+    pub message: Box<xcm::VersionedXcm<()>>,
+}
+
+/// Arguments of `pallet_xcm::limited_reserve_transfer_assets`
 #[derive(scale::Encode)]
-pub enum RuntimeCall {
-    #[codec(index = 40)]
-    Contracts(ContractsCall),
-    #[codec(index = 31)]
-    Xcm(XcmCall),
+pub struct XcmReserveTransfer {
+    pub dest: Box<xcm::VersionedMultiLocation>,
+    pub beneficiary: Box<xcm::VersionedMultiLocation>,
+    pub assets: Box<xcm::VersionedMultiAssets>,
+    pub fee_asset_item: u32,
+    pub weight_limit: WeightLimit,
 }
 
+/// Arguments of `pallet_xcm::execute`
 #[derive(scale::Encode)]
-pub enum XcmCall {
-    #[codec(index = 0)]
-    Send {
-        dest: Box<xcm::VersionedMultiLocation>,
-        message: Box<xcm::VersionedXcm<()>>,
-    },
+pub struct XcmExecute {
+    pub message: Box<xcm::VersionedXcm<()>>,
+    pub max_weight: Weight,
 }
 
+/// Arguments of `pallet_contracts::call`
 #[derive(scale::Encode)]
-pub enum ContractsCall {
-    #[codec(index = 6)]
-    Call {
-        dest: sp_runtime::MultiAddress<AccountId, ()>,
-        #[codec(compact)]
-        value: u128,
-        gas_limit: crate::Weight,
-        storage_deposit_limit: Option<u128>,
-        data: Vec<u8>,
-    },
+pub struct ContractsCall {
+    pub dest: sp_runtime::MultiAddress<AccountId, ()>,
+    #[codec(compact)]
+    pub value: u128,
+    pub gas_limit: crate::Weight,
+    pub storage_deposit_limit: Option<u128>,
+    pub data: Vec<u8>,
 }
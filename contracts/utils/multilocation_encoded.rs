@@ -1,3 +1,4 @@
+use ink::prelude::vec::Vec;
 use ink::primitives::AccountId;
 use xcm::v3::prelude::*;
 
@@ -82,3 +83,255 @@ impl From<&MultilocationEncoded> for MultiLocation {
         (*value).into()
     }
 }
+
+/// Mirrors `xcm::v3::NetworkId`, sharing its encoding
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum NetworkIdEncoded {
+    ByGenesis([u8; 32]),
+    ByFork {
+        block_number: u64,
+        block_hash: [u8; 32],
+    },
+    Polkadot,
+    Kusama,
+    Westend,
+    Rococo,
+    Wococo,
+    Ethereum {
+        #[codec(compact)]
+        chain_id: u64,
+    },
+    BitcoinCore,
+    BitcoinCash,
+}
+
+/// Mirrors the `xcm::v3::Junction`s a name can resolve to, sharing their encoding
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum JunctionEncoded {
+    #[codec(index = 0)]
+    Parachain(#[codec(compact)] u32),
+    #[codec(index = 1)]
+    AccountId32 {
+        network: Option<NetworkIdEncoded>,
+        id: [u8; 32],
+    },
+    #[codec(index = 2)]
+    AccountIndex64 {
+        network: Option<NetworkIdEncoded>,
+        #[codec(compact)]
+        index: u64,
+    },
+    #[codec(index = 3)]
+    AccountKey20 {
+        network: Option<NetworkIdEncoded>,
+        key: [u8; 20],
+    },
+    #[codec(index = 4)]
+    PalletInstance(u8),
+    #[codec(index = 5)]
+    GeneralIndex(#[codec(compact)] u128),
+    #[codec(index = 6)]
+    GeneralKey { length: u8, data: [u8; 32] },
+    #[codec(index = 9)]
+    GlobalConsensus(NetworkIdEncoded),
+}
+
+/// Location with arbitrary interior junctions (at most 8 convert to a `MultiLocation`)
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct MultilocationEncodedV2 {
+    pub parents: u8,
+    pub interior: Vec<JunctionEncoded>,
+}
+
+/// Resolving address of a name.
+/// `V1` covers `AccountId32` accounts on the relay chain & parachains, while
+/// `V2` also covers `AccountKey20` accounts, network ids, `GlobalConsensus` locations
+/// and deeper targets such as `PalletInstance`/`GeneralIndex`.
+#[derive(scale::Decode, scale::Encode, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum VersionedMultilocationEncoded {
+    V1(MultilocationEncoded),
+    V2(MultilocationEncodedV2),
+}
+
+impl From<NetworkIdEncoded> for NetworkId {
+    fn from(network: NetworkIdEncoded) -> Self {
+        match network {
+            NetworkIdEncoded::ByGenesis(hash) => ByGenesis(hash),
+            NetworkIdEncoded::ByFork {
+                block_number,
+                block_hash,
+            } => ByFork {
+                block_number,
+                block_hash,
+            },
+            NetworkIdEncoded::Polkadot => Polkadot,
+            NetworkIdEncoded::Kusama => Kusama,
+            NetworkIdEncoded::Westend => Westend,
+            NetworkIdEncoded::Rococo => Rococo,
+            NetworkIdEncoded::Wococo => Wococo,
+            NetworkIdEncoded::Ethereum { chain_id } => Ethereum { chain_id },
+            NetworkIdEncoded::BitcoinCore => BitcoinCore,
+            NetworkIdEncoded::BitcoinCash => BitcoinCash,
+        }
+    }
+}
+
+impl From<NetworkId> for NetworkIdEncoded {
+    fn from(network: NetworkId) -> Self {
+        match network {
+            ByGenesis(hash) => Self::ByGenesis(hash),
+            ByFork {
+                block_number,
+                block_hash,
+            } => Self::ByFork {
+                block_number,
+                block_hash,
+            },
+            Polkadot => Self::Polkadot,
+            Kusama => Self::Kusama,
+            Westend => Self::Westend,
+            Rococo => Self::Rococo,
+            Wococo => Self::Wococo,
+            Ethereum { chain_id } => Self::Ethereum { chain_id },
+            BitcoinCore => Self::BitcoinCore,
+            BitcoinCash => Self::BitcoinCash,
+        }
+    }
+}
+
+impl From<JunctionEncoded> for Junction {
+    fn from(jn: JunctionEncoded) -> Self {
+        match jn {
+            JunctionEncoded::Parachain(id) => Parachain(id),
+            JunctionEncoded::AccountId32 { network, id } => AccountId32 {
+                network: network.map(Into::into),
+                id,
+            },
+            JunctionEncoded::AccountIndex64 { network, index } => AccountIndex64 {
+                network: network.map(Into::into),
+                index,
+            },
+            JunctionEncoded::AccountKey20 { network, key } => AccountKey20 {
+                network: network.map(Into::into),
+                key,
+            },
+            JunctionEncoded::PalletInstance(index) => PalletInstance(index),
+            JunctionEncoded::GeneralIndex(index) => GeneralIndex(index),
+            JunctionEncoded::GeneralKey { length, data } => GeneralKey { length, data },
+            JunctionEncoded::GlobalConsensus(network) => GlobalConsensus(network.into()),
+        }
+    }
+}
+
+impl TryFrom<Junction> for JunctionEncoded {
+    type Error = ();
+
+    fn try_from(jn: Junction) -> Result<Self, Self::Error> {
+        let jn = match jn {
+            Parachain(id) => Self::Parachain(id),
+            AccountId32 { network, id } => Self::AccountId32 {
+                network: network.map(Into::into),
+                id,
+            },
+            AccountIndex64 { network, index } => Self::AccountIndex64 {
+                network: network.map(Into::into),
+                index,
+            },
+            AccountKey20 { network, key } => Self::AccountKey20 {
+                network: network.map(Into::into),
+                key,
+            },
+            PalletInstance(index) => Self::PalletInstance(index),
+            GeneralIndex(index) => Self::GeneralIndex(index),
+            GeneralKey { length, data } => Self::GeneralKey { length, data },
+            GlobalConsensus(network) => Self::GlobalConsensus(network.into()),
+            OnlyChild | Plurality { .. } => Err(())?,
+        };
+        Ok(jn)
+    }
+}
+
+impl TryFrom<MultilocationEncodedV2> for MultiLocation {
+    type Error = ();
+
+    fn try_from(loc: MultilocationEncodedV2) -> Result<Self, Self::Error> {
+        let mut interior = Here;
+        for jn in loc.interior {
+            interior.push(Junction::from(jn)).map_err(|_| ())?;
+        }
+
+        Ok(MultiLocation::new(loc.parents, interior))
+    }
+}
+
+impl TryFrom<MultiLocation> for MultilocationEncodedV2 {
+    type Error = ();
+
+    fn try_from(loc: MultiLocation) -> Result<Self, Self::Error> {
+        let interior = loc
+            .interior()
+            .iter()
+            .map(|jn| JunctionEncoded::try_from(*jn))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            parents: loc.parent_count(),
+            interior,
+        })
+    }
+}
+
+impl TryFrom<VersionedMultilocationEncoded> for MultiLocation {
+    type Error = ();
+
+    fn try_from(loc: VersionedMultilocationEncoded) -> Result<Self, Self::Error> {
+        match loc {
+            VersionedMultilocationEncoded::V1(loc) => Ok(loc.into()),
+            VersionedMultilocationEncoded::V2(loc) => loc.try_into(),
+        }
+    }
+}
+
+// Sticks to V1 whenever the location can be represented by it,
+// i.e. unless the account carries a network id which V1 can't hold
+impl TryFrom<MultiLocation> for VersionedMultilocationEncoded {
+    type Error = ();
+
+    fn try_from(loc: MultiLocation) -> Result<Self, Self::Error> {
+        let has_network = matches!(
+            loc.last(),
+            Some(AccountId32 {
+                network: Some(_),
+                ..
+            })
+        );
+        if !has_network {
+            if let Ok(loc) = MultilocationEncoded::try_from(loc) {
+                return Ok(Self::V1(loc));
+            }
+        }
+        MultilocationEncodedV2::try_from(loc).map(Self::V2)
+    }
+}
+
+impl From<MultilocationEncoded> for VersionedMultilocationEncoded {
+    fn from(loc: MultilocationEncoded) -> Self {
+        Self::V1(loc)
+    }
+}
@@ -0,0 +1,69 @@
+use ink::prelude::string::String;
+
+/// Rules a name must comply with once normalized.
+/// Each label of a subdomain (e.g. `pay` & `alice` for `pay.alice`) is checked on its own.
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct NameRules {
+    /// Minimum length of a label, in characters
+    pub min_length: u32,
+    /// Maximum length of a label, in characters
+    pub max_length: u32,
+    /// Characters allowed besides lowercase ASCII letters & digits, e.g. `-`
+    pub extra_chars: String,
+    /// Whether lowercase non-ASCII letters & digits are allowed as well
+    pub allow_unicode: bool,
+}
+
+impl Default for NameRules {
+    fn default() -> Self {
+        Self {
+            min_length: 1,
+            max_length: 64,
+            extra_chars: "-".into(),
+            allow_unicode: false,
+        }
+    }
+}
+
+impl NameRules {
+    /// Checks the normalized name against the rules
+    pub fn is_valid(&self, name: &str) -> bool {
+        name.split('.').all(|label| self.is_valid_label(label))
+    }
+
+    fn is_valid_label(&self, label: &str) -> bool {
+        let len = label.chars().count() as u32;
+        if len < self.min_length || len > self.max_length {
+            return false;
+        }
+
+        label.chars().all(|c| {
+            c.is_ascii_lowercase()
+                || c.is_ascii_digit()
+                || (c != '.' && self.extra_chars.contains(c))
+                || (self.allow_unicode && !c.is_ascii() && c.is_alphanumeric() && !c.is_uppercase())
+        })
+    }
+}
+
+/// Normalizes the name before it is validated & stored:
+/// folds fullwidth forms to their ASCII counterparts, then lowercases it.
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .map(fold_width)
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+// Maps fullwidth forms (U+FF01..U+FF5E) & the ideographic space to ASCII
+fn fold_width(c: char) -> char {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        '\u{3000}' => ' ',
+        _ => c,
+    }
+}
@@ -0,0 +1,24 @@
+/// Requests an xc-contract can make to the hub, used to price & weigh them individually
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode, Copy, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Operation {
+    GetOwner,
+    GetAddress,
+    GetPrice,
+    GetOwners,
+    GetAddresses,
+    RegisterName,
+    RenewName,
+    TransferName,
+    SetAddress,
+    SetAddressFor,
+    GetRecords,
+    SetRecords,
+    SetSubdomainOwner,
+    RevokeSubdomain,
+    LockSubdomain,
+    GetName,
+    SetPrimaryName,
+    SetManager,
+    SetOperator,
+}
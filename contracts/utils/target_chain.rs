@@ -0,0 +1,25 @@
+use crate::{ChainLocation, NetworkIdEncoded};
+
+/// Chain an address record of a name applies to
+#[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum TargetChain {
+    /// The relay chain of the hub
+    Relay,
+    /// A parachain of the hub's relay chain
+    Parachain(u32),
+    /// A chain beyond the relay-chain network, e.g. an EVM chain
+    GlobalConsensus(NetworkIdEncoded),
+}
+
+impl From<ChainLocation> for TargetChain {
+    fn from(chain: ChainLocation) -> Self {
+        match chain.para_id {
+            Some(id) => Self::Parachain(id),
+            None => Self::Relay,
+        }
+    }
+}
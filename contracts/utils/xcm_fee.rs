@@ -0,0 +1,53 @@
+use crate::ChainLocation;
+use ink::primitives::AccountId;
+use xcm::v3::prelude::*;
+
+/// How the execution of outgoing messages is paid for on the destination chain
+#[derive(Debug, PartialEq, Eq, Default, scale::Decode, scale::Encode, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum XcmFeeMode {
+    /// Relies on the destination allowing unpaid execution for the sender
+    #[default]
+    Unpaid,
+    /// Withdraws `amount` of the native asset of the `asset` chain from the sender's
+    /// account on the destination to buy execution. Any surplus is deposited back.
+    Paid { asset: ChainLocation, amount: u128 },
+}
+
+/// Fee paid for executing a message on the destination chain
+pub struct XcmFee {
+    /// Asset withdrawn to buy execution, as seen from the destination
+    pub asset: MultiAsset,
+    /// Location the surplus is deposited to, as seen from the destination
+    pub refund_to: MultiLocation,
+}
+
+impl XcmFeeMode {
+    /// Fee for executing a message sent by `sender` from `here` on the `destination` chain
+    pub fn fee_for(
+        &self,
+        sender: AccountId,
+        here: &ChainLocation,
+        destination: &ChainLocation,
+    ) -> Option<XcmFee> {
+        let Self::Paid { asset, amount } = self else {
+            return None;
+        };
+
+        let mut refund_to = here.path_from(destination);
+        refund_to
+            .push_interior(AccountId32 {
+                network: None,
+                id: *sender.as_ref(),
+            })
+            .ok()?;
+
+        Some(XcmFee {
+            asset: (asset.path_from(destination), *amount).into(),
+            refund_to,
+        })
+    }
+}
@@ -2,24 +2,111 @@
 
 #[ink::contract]
 mod xc_domain_service {
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::env::hash::Blake2x256;
+    use ink::env::DefaultEnvironment;
     use ink::prelude::string::String;
     use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
     use scale::{Decode, Encode};
+    use utils::make_reserve_transfer;
+    use utils::make_reserve_withdraw;
     use utils::make_xcm_contract_call;
-    use utils::MultilocationEncoded;
-    use xcm::v3::prelude::*;
+    use utils::normalize_name;
+    use utils::ChainLocation;
+    use utils::ChainProfile;
+    use utils::Destination;
+    use utils::FeeEstimator;
+    use utils::HandlerError;
+    use utils::NameRules;
+    use utils::Operation;
+    use utils::TargetChain;
+    use utils::VersionedMultilocationEncoded;
+    use utils::XcmFeeMode;
+    use utils::MAX_BATCH_SIZE;
 
-    const PATH_TO_HOST_CHAIN: MultiLocation = MultiLocation {
-        parents: 1,
-        interior: X1(Parachain(1)),
-    };
+    /// Default number of blocks after which an unanswered ticket expires
+    pub const DEFAULT_TICKET_TIMEOUT: BlockNumber = 100;
+    /// Maximum number of tickets returned by `get_my_tickets` in one call
+    pub const MAX_PAGE_SIZE: u32 = 100;
+    /// Default gas (ref-time) made available to a callback contract, a fraction of the
+    /// gas limit `accept_response` is called with by default (`utils::DEFAULT_GAS_LIMIT`)
+    pub const DEFAULT_CALLBACK_GAS_LIMIT: u64 = 2_000_000_000;
+    /// Default proof size made available to a callback contract, a fraction of the
+    /// proof size `accept_response` is called with by default (`utils::DEFAULT_GAS_LIMIT`)
+    pub const DEFAULT_CALLBACK_PROOF_SIZE_LIMIT: u64 = 32 * 1024;
+    /// Default weight (ref-time, proof size) of an XCM instruction executed on this chain
+    pub const DEFAULT_XCM_INSTRUCTION_WEIGHT: (u64, u64) = (1_000_000_000, 64 * 1024);
+    /// Gas (ref-time) kept for completing `accept_response` once the callback returns
+    pub const CALLBACK_GAS_RESERVE: u64 = 2_000_000_000;
 
     #[derive(scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum ReadInterface {
         Owner(Option<AccountId>),
         Address(Option<xcm::VersionedMultiLocation>),
+        Price(Balance),
+        /// Outcome of a write operation along with the amount charged
+        Write(Result<Balance, HandlerError>),
+        Owners(Vec<Option<AccountId>>),
+        Addresses(Vec<Option<xcm::VersionedMultiLocation>>),
+        /// Text records as key-value pairs
+        Records(Vec<(String, String)>),
+        /// Primary name of an account
+        Name(Option<String>),
+        /// Read operation that failed on the hub
+        Failed(HandlerError),
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum TicketStatus {
+        /// Request is sent, the hub has not responded yet
+        AwaitingResponse,
+        /// Response to a read request is available
+        Ready,
+        /// Write operation was applied on the hub
+        Succeeded,
+        /// Request was rejected by the hub
+        Failed(HandlerError),
+        /// The hub did not respond within the ticket timeout
+        Expired,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Ticket {
+        pub requester: AccountId,
+        pub created_at: BlockNumber,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum CallbackStatus {
+        /// Response has not arrived yet
+        Pending,
+        /// Response was forwarded to the callback contract
+        Delivered,
+        /// Callback contract could not be called, trapped or ran out of gas
+        Failed,
+    }
+
+    /// Contract message to be called with `(TicketId, ReadInterface)` once the response arrives
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Callback {
+        pub contract: AccountId,
+        pub selector: [u8; 4],
+        pub status: CallbackStatus,
     }
 
     pub type ReadInterfaceEncoded = Vec<u8>;
@@ -37,6 +124,16 @@ mod xc_domain_service {
         DuplicateResponse,
         FailedToDecodeResponse,
         InkEnvError,
+        RefundFailed,
+        NotAdmin,
+        NotRequester,
+        TicketExpired,
+        InvalidBatchSize,
+        InvalidName,
+        InsufficientFunds,
+        TransferFailed,
+        PaymentInEscrow,
+        RequestFailed,
     }
 
     impl From<ink::env::Error> for Error {
@@ -53,27 +150,139 @@ mod xc_domain_service {
         #[ink(topic)]
         ticket_id: TicketId,
     }
+
+    #[ink(event)]
+    pub struct PaymentRefunded {
+        #[ink(topic)]
+        ticket_id: TicketId,
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    /// `name_hash` is the Blake2x256 hash of the normalized name the request is about,
+    /// the same as in the events of the domain-service
+    #[ink(event)]
+    pub struct RequestSent {
+        #[ink(topic)]
+        ticket_id: TicketId,
+        op: Operation,
+        #[ink(topic)]
+        name_hash: Option<[u8; 32]>,
+    }
+
+    #[ink(event)]
+    pub struct Withdrawn {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct FeesForwarded {
+        asset: ChainLocation,
+        amount: Balance,
+        #[ink(topic)]
+        beneficiary: AccountId,
+    }
+
     #[ink(storage)]
     pub struct XcDomainService {
+        admin: AccountId,
         xcm_handler: AccountId,
         xcm_handler_soac: AccountId, // Try computing it on-chain
+        /// Location of the chain this contract is deployed on
+        universal_location: ChainLocation,
+        /// Location of the chain hosting the xcm-handler
+        hub_location: ChainLocation,
+        /// Chain whose native asset is the currency of this chain, i.e. the asset
+        /// payments & fees are held in
+        native_asset: ChainLocation,
+        /// Runtime call indices of this chain
+        chain_profile: ChainProfile,
+        /// Runtime call indices of the chain hosting the xcm-handler
+        hub_profile: ChainProfile,
+        /// How requests pay for their execution on the hub
+        fee_mode: XcmFeeMode,
+        /// Weight & fee parameters of this chain, priced in the hub's fee asset
+        fee_estimator: FeeEstimator,
+        /// Weight & fee parameters of the hub chain
+        hub_fee_estimator: FeeEstimator,
+        /// Gas limits of the contract call on the hub per operation, overriding `custom_weight`
+        operation_gas_limits: Mapping<Operation, (u64, u64)>,
         ticket_count: TicketId,
+        ticket_timeout: BlockNumber,
+        tickets: Mapping<TicketId, Ticket>,
+        /// Tickets of each requester, by index. A cleared ticket leaves a gap, so indexes are stable.
+        requester_to_tickets: Mapping<(AccountId, u32), TicketId>,
+        ticket_to_requester_index: Mapping<TicketId, u32>,
+        requester_ticket_count: Mapping<AccountId, u32>,
+        /// Requesters who opted for responses readable only by themselves
+        private_requesters: Mapping<AccountId, ()>,
         ticket_to_response: Mapping<TicketId, ReadInterfaceEncoded>,
+        /// Payments held until the hub reports the outcome: (payer, amount)
+        ticket_to_escrow: Mapping<TicketId, (AccountId, Balance)>,
+        /// Sum of the payments held in escrow, which can't be withdrawn
+        total_escrow: Balance,
+        /// Refunds that failed to be transferred, claimable by the payer
+        credits: Mapping<AccountId, Balance>,
+        /// Sum of the claimable refunds, which can't be withdrawn
+        total_credits: Balance,
+        ticket_to_callback: Mapping<TicketId, Callback>,
+        /// Weight made available to callbacks as (ref-time, proof size)
+        callback_gas_limit: (u64, u64),
+        /// Weight (ref-time, proof size) of an XCM instruction executed on this chain,
+        /// bounding the local execution of `forward_fees`
+        xcm_instruction_weight: (u64, u64),
+        /// Rules names are pre-validated against, mirroring the hub's
+        name_rules: NameRules,
         custom_weight: Option<(u64, u64)>,
     }
 
     impl XcDomainService {
         #[ink(constructor)]
         pub fn new(
-            xcm_handler: AccountId, 
+            admin: AccountId,
+            xcm_handler: AccountId,
             xcm_handler_soac: AccountId,
+            universal_location: ChainLocation,
+            hub_location: ChainLocation,
             custom_weight: Option<(u64, u64)>,
+            fee_estimator: FeeEstimator,
+            hub_fee_estimator: FeeEstimator,
         ) -> Self {
             Self {
+                admin,
                 xcm_handler,
                 xcm_handler_soac,
+                universal_location,
+                hub_location,
+                native_asset: universal_location,
+                chain_profile: ChainProfile::default(),
+                hub_profile: ChainProfile::default(),
+                fee_mode: XcmFeeMode::default(),
+                fee_estimator,
+                hub_fee_estimator,
+                operation_gas_limits: Mapping::default(),
                 ticket_count: 0,
+                ticket_timeout: DEFAULT_TICKET_TIMEOUT,
+                tickets: Mapping::default(),
+                requester_to_tickets: Mapping::default(),
+                ticket_to_requester_index: Mapping::default(),
+                requester_ticket_count: Mapping::default(),
+                private_requesters: Mapping::default(),
                 ticket_to_response: Mapping::default(),
+                ticket_to_escrow: Mapping::default(),
+                total_escrow: 0,
+                credits: Mapping::default(),
+                total_credits: 0,
+                ticket_to_callback: Mapping::default(),
+                callback_gas_limit: (
+                    DEFAULT_CALLBACK_GAS_LIMIT,
+                    DEFAULT_CALLBACK_PROOF_SIZE_LIMIT,
+                ),
+                xcm_instruction_weight: DEFAULT_XCM_INSTRUCTION_WEIGHT,
+                name_rules: NameRules::default(),
                 custom_weight,
             }
         }
@@ -84,11 +293,137 @@ mod xc_domain_service {
             (self.xcm_handler, self.xcm_handler_soac)
         }
 
+        /// Returns (universal-location, hub-location)
+        #[ink(message)]
+        pub fn get_locations(&self) -> (ChainLocation, ChainLocation) {
+            (self.universal_location, self.hub_location)
+        }
+
+        /// Returns the chain whose native asset is the currency of this chain
+        #[ink(message)]
+        pub fn get_native_asset(&self) -> ChainLocation {
+            self.native_asset
+        }
+
+        /// Returns (chain-profile, hub-profile)
+        #[ink(message)]
+        pub fn get_chain_profiles(&self) -> (ChainProfile, ChainProfile) {
+            (self.chain_profile, self.hub_profile)
+        }
+
+        #[ink(message)]
+        pub fn get_fee_mode(&self) -> XcmFeeMode {
+            self.fee_mode
+        }
+
+        /// Returns (fee-estimator, hub-fee-estimator)
+        #[ink(message)]
+        pub fn get_fee_estimators(&self) -> (FeeEstimator, FeeEstimator) {
+            (self.fee_estimator, self.hub_fee_estimator)
+        }
+
+        /// Returns the gas limit (ref-time, proof-size) of the contract call on the hub
+        /// for the operation. `None` means the default gas limit is used.
+        #[ink(message)]
+        pub fn get_operation_gas_limit(&self, operation: Operation) -> Option<(u64, u64)> {
+            self.operation_gas_limits
+                .get(operation)
+                .or(self.custom_weight)
+        }
+
+        /// Returns the estimated fee for executing the operation's request on the hub
+        /// and its response on this chain, in units of the hub's fee asset.
+        /// The response is assumed to be paid like the request, with the default gas limit.
+        #[ink(message)]
+        pub fn quote_fee(&self, operation: Operation) -> Balance {
+            let paid = matches!(self.fee_mode, XcmFeeMode::Paid { .. });
+            let instructions = utils::instruction_count(paid);
+
+            let request_weight = utils::transact_weight(self.gas_limit(operation));
+            let request_fee = self
+                .hub_fee_estimator
+                .estimate_message_fee(instructions, request_weight);
+            let response_fee = self
+                .fee_estimator
+                .estimate_message_fee(instructions, utils::transact_weight(None));
+
+            request_fee.saturating_add(response_fee)
+        }
+
+        /// Returns the rules names are pre-validated against before sending a request
+        #[ink(message)]
+        pub fn get_name_rules(&self) -> NameRules {
+            self.name_rules.clone()
+        }
+
+        #[ink(message)]
+        pub fn get_admin(&self) -> AccountId {
+            self.admin
+        }
+
         #[ink(message)]
         pub fn get_ticket_count(&self) -> TicketId {
             self.ticket_count
         }
 
+        /// Returns the number of blocks after which an unanswered ticket expires
+        #[ink(message)]
+        pub fn get_ticket_timeout(&self) -> BlockNumber {
+            self.ticket_timeout
+        }
+
+        /// Returns the ticket details, unless it has been cleared
+        #[ink(message)]
+        pub fn get_ticket(&self, tid: TicketId) -> Option<Ticket> {
+            self.tickets.get(tid)
+        }
+
+        /// Returns the live tickets requested by the account among the `limit` (capped at
+        /// `MAX_PAGE_SIZE`) index slots starting from `from`, in request order.
+        ///
+        /// Cleared tickets leave their slot empty, so a page may hold fewer than `limit` tickets,
+        /// but clearing never moves the remaining ones between pages.
+        #[ink(message)]
+        pub fn get_my_tickets(&self, account: AccountId, from: u32, limit: u32) -> Vec<TicketId> {
+            let count = self.requester_ticket_count.get(account).unwrap_or_default();
+            let to = from.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+            (from..to)
+                .filter_map(|idx| self.requester_to_tickets.get((account, idx)))
+                .collect()
+        }
+
+        /// Returns the number of index slots of the account's tickets, i.e. the end for paging
+        /// with `get_my_tickets`
+        #[ink(message)]
+        pub fn get_my_ticket_slots(&self, account: AccountId) -> u32 {
+            self.requester_ticket_count.get(account).unwrap_or_default()
+        }
+
+        /// Returns true if responses to the account's requests are readable only by the account
+        #[ink(message)]
+        pub fn has_private_responses(&self, account: AccountId) -> bool {
+            self.private_requesters.contains(account)
+        }
+
+        /// Returns the (ref-time, proof size) limits applied to callbacks
+        #[ink(message)]
+        pub fn get_callback_gas_limit(&self) -> (u64, u64) {
+            self.callback_gas_limit
+        }
+
+        /// Returns the (ref-time, proof size) weight of an XCM instruction executed on this chain
+        #[ink(message)]
+        pub fn get_xcm_instruction_weight(&self) -> (u64, u64) {
+            self.xcm_instruction_weight
+        }
+
+        /// Returns the callback registered for the tid along with its delivery status
+        #[ink(message)]
+        pub fn get_callback(&self, tid: TicketId) -> Option<Callback> {
+            self.ticket_to_callback.get(tid)
+        }
+
         /** Async getters STARTS here */
 
         /// Requests for the owner details of the given name.
@@ -96,13 +431,14 @@ mod xc_domain_service {
         /// Use this `TicketId` with `retrieve_owner` to get the details.
         #[ink(message)]
         pub fn get_owner(&mut self, name: String) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
             let tid = self.ticket_count;
 
             let selector = ink::selector_bytes!("get_owner");
-            let payload = (selector, tid, name).encode();
-            self.call_handler(payload)?;
+            let payload = (selector, tid, &name).encode();
+            self.call_handler(Operation::GetOwner, Some(&name), payload)?;
 
-            self.ticket_count += 1;
+            self.issue_ticket(tid);
             Ok(tid)
         }
 
@@ -111,13 +447,129 @@ mod xc_domain_service {
         /// Use this `TicketId` with `retrieve_address` to get the details.
         #[ink(message)]
         pub fn get_address(&mut self, name: String) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
             let tid = self.ticket_count;
 
             let selector = ink::selector_bytes!("get_address");
-            let payload = (selector, tid, name).encode();
-            self.call_handler(payload)?;
+            let payload = (selector, tid, &name).encode();
+            self.call_handler(Operation::GetAddress, Some(&name), payload)?;
 
-            self.ticket_count += 1;
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Same as `get_owner`, but the response is also forwarded to the `callback` contract
+        /// by calling its message identified by `selector` with `(TicketId, ReadInterface)`
+        #[ink(message)]
+        pub fn get_owner_with_callback(
+            &mut self,
+            name: String,
+            callback: AccountId,
+            selector: [u8; 4],
+        ) -> Result<TicketId, Error> {
+            let tid = self.get_owner(name)?;
+            self.register_callback(tid, callback, selector);
+            Ok(tid)
+        }
+
+        /// Same as `get_address`, but the response is also forwarded to the `callback` contract
+        /// by calling its message identified by `selector` with `(TicketId, ReadInterface)`
+        #[ink(message)]
+        pub fn get_address_with_callback(
+            &mut self,
+            name: String,
+            callback: AccountId,
+            selector: [u8; 4],
+        ) -> Result<TicketId, Error> {
+            let tid = self.get_address(name)?;
+            self.register_callback(tid, callback, selector);
+            Ok(tid)
+        }
+
+        /// Requests for the owner details of all the given names (up to `MAX_BATCH_SIZE`)
+        /// in a single message. Returns `TicketId` for the request.
+        /// Use this `TicketId` with `retrieve_owners` to get the details, in the same order.
+        #[ink(message)]
+        pub fn get_owners(&mut self, names: Vec<String>) -> Result<TicketId, Error> {
+            Self::ensure_batch_size(&names)?;
+            let names = names
+                .iter()
+                .map(|name| self.normalize(name))
+                .collect::<Result<Vec<_>, _>>()?;
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("get_owners");
+            let payload = (selector, tid, names).encode();
+            self.call_handler(Operation::GetOwners, None, payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Requests for the resolving address details of all the given names
+        /// (up to `MAX_BATCH_SIZE`) in a single message. Returns `TicketId` for the request.
+        /// Use this `TicketId` with `retrieve_addresses` to get the details, in the same order.
+        #[ink(message)]
+        pub fn get_addresses(&mut self, names: Vec<String>) -> Result<TicketId, Error> {
+            Self::ensure_batch_size(&names)?;
+            let names = names
+                .iter()
+                .map(|name| self.normalize(name))
+                .collect::<Result<Vec<_>, _>>()?;
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("get_addresses");
+            let payload = (selector, tid, names).encode();
+            self.call_handler(Operation::GetAddresses, None, payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Requests for the price of registering the given name for `years`.
+        /// Returns `TicketId` for the request.
+        /// Use this `TicketId` with `retrieve_price` to get the quote.
+        #[ink(message)]
+        pub fn get_price(&mut self, name: String, years: u8) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("get_price");
+            let payload = (selector, tid, &name, years).encode();
+            self.call_handler(Operation::GetPrice, Some(&name), payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Requests for the text records of the given name.
+        /// Returns `TicketId` for the request.
+        /// Use this `TicketId` with `retrieve_records` to get the records.
+        #[ink(message)]
+        pub fn get_records(&mut self, name: String) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("get_records");
+            let payload = (selector, tid, &name).encode();
+            self.call_handler(Operation::GetRecords, Some(&name), payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Requests for the primary name of the given account of this chain.
+        /// Returns `TicketId` for the request.
+        /// Use this `TicketId` with `retrieve_name` to get the name.
+        #[ink(message)]
+        pub fn get_name(&mut self, account: AccountId) -> Result<TicketId, Error> {
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("get_name");
+            let payload = (selector, tid, account).encode();
+            self.call_handler(Operation::GetName, None, payload)?;
+
+            self.issue_ticket(tid);
             Ok(tid)
         }
 
@@ -130,6 +582,7 @@ mod xc_domain_service {
         pub fn retrieve_owner(&self, tid: TicketId) -> Result<Option<AccountId>, Error> {
             match self.read_response(tid)? {
                 ReadInterface::Owner(rs) => Ok(rs),
+                ReadInterface::Failed(_) => Err(Error::RequestFailed),
                 _ => Err(Error::TicketIdMismatch),
             }
         }
@@ -142,18 +595,105 @@ mod xc_domain_service {
         ) -> Result<Option<xcm::VersionedMultiLocation>, Error> {
             match self.read_response(tid)? {
                 ReadInterface::Address(rs) => Ok(rs),
+                ReadInterface::Failed(_) => Err(Error::RequestFailed),
+                _ => Err(Error::TicketIdMismatch),
+            }
+        }
+
+        /// Get the owner details of the batch associated with the TicketId (if valid)
+        #[ink(message)]
+        pub fn retrieve_owners(&self, tid: TicketId) -> Result<Vec<Option<AccountId>>, Error> {
+            match self.read_response(tid)? {
+                ReadInterface::Owners(rs) => Ok(rs),
+                ReadInterface::Failed(_) => Err(Error::RequestFailed),
+                _ => Err(Error::TicketIdMismatch),
+            }
+        }
+
+        /// Get the address details of the batch associated with the TicketId (if valid)
+        #[ink(message)]
+        pub fn retrieve_addresses(
+            &self,
+            tid: TicketId,
+        ) -> Result<Vec<Option<xcm::VersionedMultiLocation>>, Error> {
+            match self.read_response(tid)? {
+                ReadInterface::Addresses(rs) => Ok(rs),
+                ReadInterface::Failed(_) => Err(Error::RequestFailed),
+                _ => Err(Error::TicketIdMismatch),
+            }
+        }
+
+        /// Get the price quote associated with the TicketId (if valid)
+        #[ink(message)]
+        pub fn retrieve_price(&self, tid: TicketId) -> Result<Balance, Error> {
+            match self.read_response(tid)? {
+                ReadInterface::Price(rs) => Ok(rs),
+                ReadInterface::Failed(_) => Err(Error::RequestFailed),
+                _ => Err(Error::TicketIdMismatch),
+            }
+        }
+
+        /// Get the text records associated with the TicketId (if valid)
+        #[ink(message)]
+        pub fn retrieve_records(&self, tid: TicketId) -> Result<Vec<(String, String)>, Error> {
+            match self.read_response(tid)? {
+                ReadInterface::Records(rs) => Ok(rs),
+                ReadInterface::Failed(_) => Err(Error::RequestFailed),
+                _ => Err(Error::TicketIdMismatch),
+            }
+        }
+
+        /// Get the primary name associated with the TicketId (if valid)
+        #[ink(message)]
+        pub fn retrieve_name(&self, tid: TicketId) -> Result<Option<String>, Error> {
+            match self.read_response(tid)? {
+                ReadInterface::Name(rs) => Ok(rs),
+                ReadInterface::Failed(_) => Err(Error::RequestFailed),
+                _ => Err(Error::TicketIdMismatch),
+            }
+        }
+
+        /// Get the outcome of the write operation associated with the TicketId (if valid).
+        /// On success, it carries the amount charged by the hub.
+        #[ink(message)]
+        pub fn retrieve_write_outcome(
+            &self,
+            tid: TicketId,
+        ) -> Result<Result<Balance, HandlerError>, Error> {
+            match self.read_response(tid)? {
+                ReadInterface::Write(rs) => Ok(rs),
+                ReadInterface::Failed(_) => Err(Error::RequestFailed),
                 _ => Err(Error::TicketIdMismatch),
             }
         }
 
+        /// Returns the status of the request associated with the tid (if valid)
+        #[ink(message)]
+        pub fn get_ticket_status(&self, tid: TicketId) -> Result<TicketStatus, Error> {
+            let status = match self.read_response(tid) {
+                Ok(ReadInterface::Write(Ok(_))) => TicketStatus::Succeeded,
+                Ok(ReadInterface::Write(Err(e))) => TicketStatus::Failed(e),
+                Ok(ReadInterface::Failed(e)) => TicketStatus::Failed(e),
+                Ok(_) => TicketStatus::Ready,
+                Err(Error::AwaitingResponse) => TicketStatus::AwaitingResponse,
+                Err(Error::TicketExpired) => TicketStatus::Expired,
+                Err(e) => Err(e)?,
+            };
+            Ok(status)
+        }
+
         /// Returns whole response associated with the tid (if valid)
         #[ink(message)]
         pub fn read_response(&self, tid: TicketId) -> Result<ReadInterface, Error> {
-            if tid >= self.ticket_count {
+            let Some(ticket) = self.tickets.get(tid) else {
                 Err(Error::InvalidTicketId)?
-            }
+            };
+            self.ensure_readable(&ticket)?;
 
             let Some(response) = self.ticket_to_response.get(tid) else {
+                if self.is_expired(&ticket) {
+                    Err(Error::TicketExpired)?
+                }
                 Err(Error::AwaitingResponse)?
             };
             ReadInterface::decode(&mut &response[..]).map_err(|_| Error::FailedToDecodeResponse)
@@ -162,53 +702,539 @@ mod xc_domain_service {
         /// Returns the response associated with the tid in encoded format
         #[ink(message)]
         pub fn read_raw_response(&self, tid: TicketId) -> Option<ReadInterfaceEncoded> {
+            let ticket = self.tickets.get(tid)?;
+            self.ensure_readable(&ticket).ok()?;
             self.ticket_to_response.get(tid)
         }
 
+        /// Returns the (payer, amount) held in escrow for the tid, until its response arrives
+        #[ink(message)]
+        pub fn get_escrow(&self, tid: TicketId) -> Option<(AccountId, Balance)> {
+            self.ticket_to_escrow.get(tid)
+        }
+
+        /// Returns the refunds the account can claim with `claim_credit`
+        #[ink(message)]
+        pub fn get_credit(&self, account: AccountId) -> Balance {
+            self.credits.get(account).unwrap_or_default()
+        }
+
+        /// Returns the balance the admin can withdraw, i.e. excluding payments held in escrow
+        /// and claimable refunds
+        #[ink(message)]
+        pub fn get_withdrawable_balance(&self) -> Balance {
+            self.env()
+                .balance()
+                .saturating_sub(self.total_escrow)
+                .saturating_sub(self.total_credits)
+        }
+
         /** Getters request fulfill ENDS here */
 
         /** Async setters STARTS here */
 
-        /// @note For simplicity, Assumption is made that the name will be successfully registered
-        /// and therefore refund case is not handled here!
+        /// Registers the name for given number of years.
+        /// The transferred value is checked against the hub's price (see `get_price`).
+        /// Payment is held in escrow until the hub responds: it is fully refunded if
+        /// the registration fails, otherwise any amount above the price is refunded.
+        /// Returns `TicketId` for the request.
         #[ink(message, payable)]
-        pub fn register_name(&mut self, name: String) -> Result<(), Error> {
-            if self.env().transferred_value() < 80 {
+        pub fn register_name(&mut self, name: String, years: u8) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let paid = self.env().transferred_value();
+            if paid == 0 {
                 return Err(Error::PaymentNotReceived);
             }
+            let tid = self.ticket_count;
 
             let selector = ink::selector_bytes!("register_name");
             let caller = self.env().caller();
-            let payload = (selector, caller, name).encode();
+            let payload = (selector, tid, caller, &name, years, paid).encode();
+            self.call_handler(Operation::RegisterName, Some(&name), payload)?;
+
+            self.hold_payment(tid, caller, paid);
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Extends the registration of the name by given number of years.
+        /// Payment is handled the same way as in `register_name`.
+        /// Returns `TicketId` for the request.
+        #[ink(message, payable)]
+        pub fn renew_name(&mut self, name: String, years: u8) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let paid = self.env().transferred_value();
+            if paid == 0 {
+                return Err(Error::PaymentNotReceived);
+            }
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("renew_name");
+            let caller = self.env().caller();
+            let payload = (selector, tid, caller, &name, years, paid).encode();
+            self.call_handler(Operation::RenewName, Some(&name), payload)?;
 
-            self.call_handler(payload)
+            self.hold_payment(tid, caller, paid);
+            self.issue_ticket(tid);
+            Ok(tid)
         }
 
+        /// Returns `TicketId` for the request.
         #[ink(message)]
-        pub fn transfer_name(&mut self, name: String, receiver: AccountId) -> Result<(), Error> {
+        pub fn transfer_name(
+            &mut self,
+            name: String,
+            receiver: AccountId,
+        ) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let tid = self.ticket_count;
+
             let selector = ink::selector_bytes!("transfer_name");
             let caller = self.env().caller();
-            let payload = (selector, caller, name, receiver).encode();
+            let payload = (selector, tid, caller, &name, receiver).encode();
+            self.call_handler(Operation::TransferName, Some(&name), payload)?;
 
-            self.call_handler(payload)
+            self.issue_ticket(tid);
+            Ok(tid)
         }
 
-        /// Allow setting the resolving address in `MultiLocation` format
+        /// Allow setting the resolving address in `MultiLocation` format, relative to this chain.
+        /// Returns `TicketId` for the request.
         #[ink(message)]
         pub fn set_address(
             &mut self,
             name: String,
-            loc: MultilocationEncoded,
-        ) -> Result<(), Error> {
+            loc: VersionedMultilocationEncoded,
+        ) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let tid = self.ticket_count;
+
             let selector = ink::selector_bytes!("set_address");
             let caller = self.env().caller();
-            let payload = (selector, caller, name, loc).encode();
+            let payload = (selector, tid, caller, &name, loc).encode();
+            self.call_handler(Operation::SetAddress, Some(&name), payload)?;
 
-            self.call_handler(payload)
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Allow setting the address record of the name for the given chain, `None` removes it.
+        /// `loc` is relative to this chain.
+        /// Returns `TicketId` for the request.
+        #[ink(message)]
+        pub fn set_address_for(
+            &mut self,
+            name: String,
+            chain: TargetChain,
+            loc: Option<VersionedMultilocationEncoded>,
+        ) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("set_address_for");
+            let caller = self.env().caller();
+            let payload = (selector, tid, caller, &name, chain, loc).encode();
+            self.call_handler(Operation::SetAddressFor, Some(&name), payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Allow updating the text records of the name: `Some(value)` sets the record `key`,
+        /// `None` removes it. Returns `TicketId` for the request.
+        #[ink(message)]
+        pub fn set_records(
+            &mut self,
+            name: String,
+            records: Vec<(String, Option<String>)>,
+        ) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("set_records");
+            let caller = self.env().caller();
+            let payload = (selector, tid, caller, &name, records).encode();
+            self.call_handler(Operation::SetRecords, Some(&name), payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Allow setting the primary name of the caller, which must own the name.
+        /// `None` unsets it. Returns `TicketId` for the request.
+        #[ink(message)]
+        pub fn set_primary_name(&mut self, name: Option<String>) -> Result<TicketId, Error> {
+            let name = name.map(|name| self.normalize(&name)).transpose()?;
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("set_primary_name");
+            let caller = self.env().caller();
+            let payload = (selector, tid, caller, &name).encode();
+            self.call_handler(Operation::SetPrimaryName, name.as_deref(), payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Allow creating the subdomain `label.name` or reassigning it to `owner`.
+        /// Returns `TicketId` for the request.
+        #[ink(message)]
+        pub fn set_subdomain_owner(
+            &mut self,
+            name: String,
+            label: String,
+            owner: AccountId,
+        ) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let label = self.normalize(&label)?;
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("set_subdomain_owner");
+            let caller = self.env().caller();
+            let payload = (selector, tid, caller, &name, label, owner).encode();
+            self.call_handler(Operation::SetSubdomainOwner, Some(&name), payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Allow removing the subdomain `label.name`, unless it is locked.
+        /// Returns `TicketId` for the request.
+        #[ink(message)]
+        pub fn revoke_subdomain(&mut self, name: String, label: String) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let label = self.normalize(&label)?;
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("revoke_subdomain");
+            let caller = self.env().caller();
+            let payload = (selector, tid, caller, &name, label).encode();
+            self.call_handler(Operation::RevokeSubdomain, Some(&name), payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Allow locking the subdomain `label.name` for good, so that it can no longer be
+        /// reassigned or revoked by the owner of `name`. Returns `TicketId` for the request.
+        #[ink(message)]
+        pub fn lock_subdomain(&mut self, name: String, label: String) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let label = self.normalize(&label)?;
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("lock_subdomain");
+            let caller = self.env().caller();
+            let payload = (selector, tid, caller, &name, label).encode();
+            self.call_handler(Operation::LockSubdomain, Some(&name), payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Allow setting the manager of the name, who can transfer it and update its addresses
+        /// on the hub. `manager` is a hub account, `None` removes it.
+        /// Returns `TicketId` for the request.
+        #[ink(message)]
+        pub fn set_manager(
+            &mut self,
+            name: String,
+            manager: Option<AccountId>,
+        ) -> Result<TicketId, Error> {
+            let name = self.normalize(&name)?;
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("set_manager");
+            let caller = self.env().caller();
+            let payload = (selector, tid, caller, &name, manager).encode();
+            self.call_handler(Operation::SetManager, Some(&name), payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
+        }
+
+        /// Allow (or disallow) `operator`, a hub account, to manage all the names of the caller.
+        /// Returns `TicketId` for the request.
+        #[ink(message)]
+        pub fn set_operator(
+            &mut self,
+            operator: AccountId,
+            approved: bool,
+        ) -> Result<TicketId, Error> {
+            let tid = self.ticket_count;
+
+            let selector = ink::selector_bytes!("set_operator");
+            let caller = self.env().caller();
+            let payload = (selector, tid, caller, operator, approved).encode();
+            self.call_handler(Operation::SetOperator, None, payload)?;
+
+            self.issue_ticket(tid);
+            Ok(tid)
         }
 
         /** Async setters ENDS here */
 
+        /// Removes the ticket along with its response, releasing the storage deposit.
+        /// Only the requester can clear a ticket, once its response is received or it has expired.
+        /// A ticket still holding a payment can't be cleared: the payment is settled once the
+        /// response arrives, or by the admin (see `resolve_escrow`).
+        #[ink(message)]
+        pub fn clear_ticket(&mut self, tid: TicketId) -> Result<(), Error> {
+            let Some(ticket) = self.tickets.get(tid) else {
+                return Err(Error::InvalidTicketId);
+            };
+            if ticket.requester != self.env().caller() {
+                return Err(Error::NotRequester);
+            }
+            if !self.ticket_to_response.contains(tid) && !self.is_expired(&ticket) {
+                return Err(Error::AwaitingResponse);
+            }
+
+            if self.ticket_to_escrow.contains(tid) {
+                return Err(Error::PaymentInEscrow);
+            }
+
+            self.tickets.remove(tid);
+            self.ticket_to_response.remove(tid);
+            self.ticket_to_callback.remove(tid);
+
+            self.remove_requester_ticket(&ticket.requester, tid);
+
+            Ok(())
+        }
+
+        /// Transfers the refunds that could not be paid out when the responses arrived
+        #[ink(message)]
+        pub fn claim_credit(&mut self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let Some(amount) = self.credits.take(caller) else {
+                return Ok(());
+            };
+            self.total_credits = self.total_credits.saturating_sub(amount);
+            self.env()
+                .transfer(caller, amount)
+                .map_err(|_| Error::RefundFailed)
+        }
+
+        /// Allows the caller to restrict reading the responses of its requests to itself
+        #[ink(message)]
+        pub fn set_private_responses(&mut self, enabled: bool) {
+            let caller = self.env().caller();
+            if enabled {
+                self.private_requesters.insert(caller, &());
+            } else {
+                self.private_requesters.remove(caller);
+            }
+        }
+
+        /// ADMIN use only! Allows updating the location of this chain and the hub chain,
+        /// which drive the routing of requests to the xcm-handler
+        #[ink(message)]
+        pub fn set_locations(
+            &mut self,
+            universal_location: ChainLocation,
+            hub_location: ChainLocation,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.universal_location = universal_location;
+            self.hub_location = hub_location;
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating the chain whose native asset is the currency of
+        /// this chain, e.g. the relay chain for a chain using the relay token
+        #[ink(message)]
+        pub fn set_native_asset(&mut self, native_asset: ChainLocation) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.native_asset = native_asset;
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating the runtime call indices of this chain and the hub chain
+        #[ink(message)]
+        pub fn set_chain_profiles(
+            &mut self,
+            chain_profile: ChainProfile,
+            hub_profile: ChainProfile,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.chain_profile = chain_profile;
+            self.hub_profile = hub_profile;
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows choosing how requests pay for their execution on the hub.
+        /// Paid fees are withdrawn from this contract's account on the hub.
+        #[ink(message)]
+        pub fn set_fee_mode(&mut self, fee_mode: XcmFeeMode) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.fee_mode = fee_mode;
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating the weight & fee parameters of this chain and the hub chain
+        #[ink(message)]
+        pub fn set_fee_estimators(
+            &mut self,
+            fee_estimator: FeeEstimator,
+            hub_fee_estimator: FeeEstimator,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.fee_estimator = fee_estimator;
+            self.hub_fee_estimator = hub_fee_estimator;
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows overriding the gas limit (ref-time, proof-size) of the
+        /// contract call on the hub for the operation. Pass `None` to remove the override.
+        #[ink(message)]
+        pub fn set_operation_gas_limit(
+            &mut self,
+            operation: Operation,
+            gas_limit: Option<(u64, u64)>,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if let Some(gas_limit) = gas_limit {
+                self.operation_gas_limits.insert(operation, &gas_limit);
+            } else {
+                self.operation_gas_limits.remove(operation);
+            }
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating the ref-time & proof size limits applied to callbacks.
+        /// The proof size can't be capped to what remains, so it must stay well below the proof
+        /// size `accept_response` is called with by the xcm-handler. Zero limits disable callbacks.
+        #[ink(message)]
+        pub fn set_callback_gas_limit(
+            &mut self,
+            ref_time_limit: u64,
+            proof_size_limit: u64,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.callback_gas_limit = (ref_time_limit, proof_size_limit);
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating the weight of an XCM instruction executed on this
+        /// chain, to be kept in line with the chain's XCM weigher
+        #[ink(message)]
+        pub fn set_xcm_instruction_weight(
+            &mut self,
+            ref_time: u64,
+            proof_size: u64,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.xcm_instruction_weight = (ref_time, proof_size);
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating the rules names are pre-validated against,
+        /// to be kept in line with the hub's
+        #[ink(message)]
+        pub fn set_name_rules(&mut self, name_rules: NameRules) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.name_rules = name_rules;
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows updating the ticket timeout (in blocks)
+        #[ink(message)]
+        pub fn set_ticket_timeout(&mut self, ticket_timeout: BlockNumber) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.ticket_timeout = ticket_timeout;
+            Ok(())
+        }
+
+        /// ADMIN use only! Settles the payment held for a ticket whose outcome is unknown,
+        /// i.e. expired without a response or with an undecodable one, refunding `refund`
+        /// to the payer and retaining the rest. The admin is expected to check the hub's
+        /// state first, as an expired request may still have been applied there.
+        #[ink(message)]
+        pub fn resolve_escrow(&mut self, tid: TicketId, refund: Balance) -> Result<(), Error> {
+            self.ensure_admin()?;
+            let Some(ticket) = self.tickets.get(tid) else {
+                return Err(Error::InvalidTicketId);
+            };
+            if !self.ticket_to_response.contains(tid) && !self.is_expired(&ticket) {
+                return Err(Error::AwaitingResponse);
+            }
+            let Some((payer, paid)) = self.get_escrow(tid) else {
+                return Err(Error::InvalidTicketId);
+            };
+            if refund > paid {
+                return Err(Error::InsufficientFunds);
+            }
+
+            self.release_payment(tid);
+            self.refund(tid, payer, refund)
+        }
+
+        /// ADMIN use only! Allows withdrawing the collected fees, except for the payments
+        /// held in escrow
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance, to: AccountId) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if amount > self.get_withdrawable_balance() {
+                return Err(Error::InsufficientFunds);
+            }
+            self.env()
+                .transfer(to, amount)
+                .map_err(|_| Error::TransferFailed)?;
+
+            self.env().emit_event(Withdrawn { to, amount });
+            Ok(())
+        }
+
+        /// ADMIN use only! Forwards collected fees to `beneficiary` on the hub, in the currency
+        /// of this chain (see `get_native_asset`). The amount is taken out of this contract's
+        /// balance, so it is bound by `get_withdrawable_balance` in the same way as `withdraw`.
+        /// The fees are reserve-transferred if this chain is the reserve of its currency,
+        /// otherwise they are withdrawn via the reserve chain.
+        #[ink(message)]
+        pub fn forward_fees(
+            &mut self,
+            amount: Balance,
+            beneficiary: AccountId,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if amount > self.get_withdrawable_balance() {
+                return Err(Error::InsufficientFunds);
+            }
+
+            let asset = self.native_asset;
+            let (here, hub) = (&self.universal_location, &self.hub_location);
+            if asset == *here {
+                make_reserve_transfer::<Self>(
+                    &self.chain_profile,
+                    here,
+                    hub,
+                    &asset,
+                    amount,
+                    beneficiary,
+                )?;
+            } else {
+                let (ref_time, proof_size) = self.xcm_instruction_weight;
+                let instruction_weight = utils::Weight::from_parts(ref_time, proof_size);
+                make_reserve_withdraw::<Self>(
+                    &self.chain_profile,
+                    here,
+                    hub,
+                    &asset,
+                    amount,
+                    beneficiary,
+                    instruction_weight,
+                )?;
+            }
+
+            self.env().emit_event(FeesForwarded {
+                asset,
+                amount,
+                beneficiary,
+            });
+            Ok(())
+        }
+
         /// For xcm-handler response only!
         #[ink(message)]
         pub fn accept_response(
@@ -218,31 +1244,239 @@ mod xc_domain_service {
         ) -> Result<(), Error> {
             self.ensure_handler()?;
 
-            if tid >= self.ticket_count {
+            // Late responses are accepted as long as the ticket is not cleared
+            if !self.tickets.contains(tid) {
                 Err(Error::InvalidTicketId)?
             } else if self.ticket_to_response.contains(tid) {
                 Err(Error::DuplicateResponse)?
             }
 
             self.ticket_to_response.insert(tid, &response);
+            self.settle_escrow(tid, &response);
 
             // Emit event to announce response availability
             self.env().emit_event(ResponseReceived { ticket_id: tid });
 
+            self.deliver_callback(tid, &response);
+
             Ok(())
         }
 
-        fn call_handler(&mut self, payload: Vec<u8>) -> Result<(), Error> {
-            let wt = self.custom_weight.map(|(x,y)| utils::Weight::from_parts(x,y));
-            
+        fn register_callback(&mut self, tid: TicketId, contract: AccountId, selector: [u8; 4]) {
+            let callback = Callback {
+                contract,
+                selector,
+                status: CallbackStatus::Pending,
+            };
+            self.ticket_to_callback.insert(tid, &callback);
+        }
+
+        /// Forwards the response to the callback contract registered for the tid (if any).
+        /// A failing callback never reverts the response, its outcome is recorded instead.
+        fn deliver_callback(&mut self, tid: TicketId, response: &ReadInterfaceEncoded) {
+            let Some(mut callback) = self.ticket_to_callback.get(tid) else {
+                return;
+            };
+
+            // Both weight dimensions are bounded, so that a callback can't exhaust the weight
+            // left for storing the response. A limit above the remaining gas would trap this
+            // call, so the ref-time is capped to the remaining gas, less a reserve.
+            // Note: a zero limit stands for all the remaining weight, so the callback is skipped.
+            let (ref_time_limit, proof_size_limit) = self.callback_gas_limit;
+            let ref_time_limit =
+                ref_time_limit.min(self.env().gas_left().saturating_sub(CALLBACK_GAS_RESERVE));
+            let delivered = match ReadInterface::decode(&mut &response[..]) {
+                Ok(_) if ref_time_limit == 0 || proof_size_limit == 0 => false,
+                Ok(rs) => build_call::<DefaultEnvironment>()
+                    .call_v2(callback.contract)
+                    .ref_time_limit(ref_time_limit)
+                    .proof_size_limit(proof_size_limit)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(callback.selector))
+                            .push_arg(tid)
+                            .push_arg(rs),
+                    )
+                    .returns::<()>()
+                    .try_invoke()
+                    .is_ok_and(|out| out.is_ok()),
+                Err(_) => false,
+            };
+
+            callback.status = if delivered {
+                CallbackStatus::Delivered
+            } else {
+                CallbackStatus::Failed
+            };
+            self.ticket_to_callback.insert(tid, &callback);
+        }
+
+        /// Refunds the payer based on the reported outcome of the write operation:
+        /// the whole payment on failure, and the amount above the charged price on success.
+        /// A refund that can't be transferred is credited to the payer instead, so that
+        /// the response is never lost (see `claim_credit`).
+        fn settle_escrow(&mut self, tid: TicketId, response: &ReadInterfaceEncoded) {
+            let Some((payer, paid)) = self.get_escrow(tid) else {
+                return;
+            };
+
+            let refund = match ReadInterface::decode(&mut &response[..]) {
+                Ok(ReadInterface::Write(Ok(charged))) => paid.saturating_sub(charged),
+                Ok(ReadInterface::Write(Err(_))) => paid,
+                // Keep the payment held if the outcome is unknown
+                _ => return,
+            };
+            self.release_payment(tid);
+
+            if self.refund(tid, payer, refund).is_err() {
+                let credit = self.get_credit(payer).saturating_add(refund);
+                self.credits.insert(payer, &credit);
+                self.total_credits = self.total_credits.saturating_add(refund);
+            }
+        }
+
+        /// Holds the payment for the tid in escrow until the hub reports the outcome
+        fn hold_payment(&mut self, tid: TicketId, payer: AccountId, amount: Balance) {
+            self.ticket_to_escrow.insert(tid, &(payer, amount));
+            self.total_escrow = self.total_escrow.saturating_add(amount);
+        }
+
+        /// Removes the payment held for the tid from escrow, returning it
+        fn release_payment(&mut self, tid: TicketId) -> Option<(AccountId, Balance)> {
+            let (payer, amount) = self.ticket_to_escrow.get(tid)?;
+            self.ticket_to_escrow.remove(tid);
+            self.total_escrow = self.total_escrow.saturating_sub(amount);
+            Some((payer, amount))
+        }
+
+        fn refund(&mut self, tid: TicketId, to: AccountId, amount: Balance) -> Result<(), Error> {
+            if amount == 0 {
+                return Ok(());
+            }
+            self.env()
+                .transfer(to, amount)
+                .map_err(|_| Error::RefundFailed)?;
+
+            self.env().emit_event(PaymentRefunded {
+                ticket_id: tid,
+                to,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Records the request for `tid` and advances the ticket counter
+        fn issue_ticket(&mut self, tid: TicketId) {
+            let requester = self.env().caller();
+            let ticket = Ticket {
+                requester,
+                created_at: self.env().block_number(),
+            };
+            self.tickets.insert(tid, &ticket);
+
+            let count = self
+                .requester_ticket_count
+                .get(requester)
+                .unwrap_or_default();
+            self.requester_to_tickets.insert((requester, count), &tid);
+            self.ticket_to_requester_index.insert(tid, &count);
+            self.requester_ticket_count.insert(requester, &(count + 1));
+
+            self.ticket_count += 1;
+        }
+
+        /// Removes the ticket from the tickets of the requester, leaving its slot empty so that
+        /// the other tickets keep their index. Only the last slot is given back.
+        fn remove_requester_ticket(&mut self, requester: &AccountId, tid: TicketId) {
+            let Some(idx) = self.ticket_to_requester_index.take(tid) else {
+                return;
+            };
+            self.requester_to_tickets.remove((requester, idx));
+
+            let last = self
+                .requester_ticket_count
+                .get(requester)
+                .unwrap_or_default()
+                - 1;
+            if idx == last && last == 0 {
+                self.requester_ticket_count.remove(requester);
+            } else if idx == last {
+                self.requester_ticket_count.insert(requester, &last);
+            }
+        }
+
+        fn ensure_readable(&self, ticket: &Ticket) -> Result<(), Error> {
+            if self.private_requesters.contains(ticket.requester)
+                && self.env().caller() != ticket.requester
+            {
+                Err(Error::NotRequester)?
+            }
+            Ok(())
+        }
+
+        fn is_expired(&self, ticket: &Ticket) -> bool {
+            self.env().block_number() >= ticket.created_at.saturating_add(self.ticket_timeout)
+        }
+
+        /// Normalizes the name and checks it against the naming rules,
+        /// so that invalid names are rejected before paying for the message
+        fn normalize(&self, name: &str) -> Result<String, Error> {
+            let name = normalize_name(name);
+            if !self.name_rules.is_valid(&name) {
+                return Err(Error::InvalidName);
+            }
+            Ok(name)
+        }
+
+        fn ensure_batch_size(names: &[String]) -> Result<(), Error> {
+            if names.is_empty() || names.len() > MAX_BATCH_SIZE {
+                Err(Error::InvalidBatchSize)?
+            }
+            Ok(())
+        }
+
+        fn gas_limit(&self, operation: Operation) -> Option<utils::Weight> {
+            self.get_operation_gas_limit(operation)
+                .map(|(x, y)| utils::Weight::from_parts(x, y))
+        }
+
+        /// Sends the request for the next ticket to the xcm-handler
+        fn call_handler(
+            &mut self,
+            operation: Operation,
+            name: Option<&str>,
+            payload: Vec<u8>,
+        ) -> Result<(), Error> {
+            let wt = self.gas_limit(operation);
+
+            let (here, hub) = (&self.universal_location, &self.hub_location);
+            let destination = Destination {
+                path: hub.path_from(here),
+                profile: self.hub_profile,
+                fee: self.fee_mode.fee_for(self.env().account_id(), here, hub),
+            };
+
             make_xcm_contract_call::<Self>(
-                PATH_TO_HOST_CHAIN.into(),
+                &self.chain_profile,
+                destination,
                 self.xcm_handler,
                 payload,
                 0,
                 wt,
-            )
-            .map_err(Into::into)
+            )?;
+
+            self.env().emit_event(RequestSent {
+                ticket_id: self.ticket_count,
+                op: operation,
+                name_hash: name.map(|name| self.env().hash_bytes::<Blake2x256>(name.as_bytes())),
+            });
+            Ok(())
+        }
+
+        fn ensure_admin(&self) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                Err(Error::NotAdmin)?
+            }
+            Ok(())
         }
 
         fn ensure_handler(&self) -> Result<(), Error> {
@@ -2,12 +2,23 @@
 
 #[ink::contract]
 mod xcm_handler {
+    use ink::env::hash::Blake2x256;
     use ink::prelude::string::String;
     use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
-    use scale::Encode;
+    use scale::{Compact, Encode};
     use utils::make_xcm_contract_call;
+    use utils::ChainLocation;
+    use utils::ChainProfile;
+    use utils::Destination;
+    use utils::DomainServiceError;
+    use utils::HandlerError as Error;
     use utils::MultilocationEncoded;
+    use utils::Operation;
+    use utils::TargetChain;
+    use utils::VersionedMultilocationEncoded;
+    use utils::XcmFeeMode;
+    use utils::MAX_BATCH_SIZE;
     use xcm::v3::prelude::*;
     use xcm::VersionedMultiLocation;
 
@@ -19,28 +30,53 @@ mod xcm_handler {
     pub enum ReadInterface {
         Owner(Option<AccountId>),
         Address(Option<VersionedMultiLocation>),
+        Price(Balance),
+        /// Outcome of a write operation along with the amount charged
+        Write(Result<Balance, Error>),
+        Owners(Vec<Option<AccountId>>),
+        Addresses(Vec<Option<VersionedMultiLocation>>),
+        /// Text records as key-value pairs
+        Records(Vec<(String, String)>),
+        /// Primary name of an account
+        Name(Option<String>),
+        /// Read operation that failed on the hub
+        Failed(Error),
     }
 
-    #[derive(scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub enum Error {
-        NotAdmin,
-        UnknownCaller,
-        CallRuntimeFailed,
-        InkEnvError,
-        DomainService(u8),
-        UnsupportedXcmVersion,
-        UnsupportedMultiLocationFormat,
-        ReanchoringFailed,
+    /// Emitted once a request of an xc-contract is processed. `result` is the outcome
+    /// reported back for write requests and the error of failed read requests, and `None`
+    /// for read requests that succeeded.
+    #[ink(event)]
+    pub struct RequestHandled {
+        #[ink(topic)]
+        ticket_id: TicketId,
+        #[ink(topic)]
+        origin: MultilocationEncoded,
+        op: Operation,
+        result: Option<Result<Balance, Error>>,
     }
 
-    impl From<ink::env::Error> for Error {
-        fn from(e: ink::env::Error) -> Self {
-            match e {
-                ink::env::Error::CallRuntimeFailed => Error::CallRuntimeFailed,
-                _ => Error::InkEnvError,
-            }
-        }
+    /// Emitted once the response to a request is sent back to the xc-contract
+    #[ink(event)]
+    pub struct ResponseDispatched {
+        #[ink(topic)]
+        ticket_id: TicketId,
+        #[ink(topic)]
+        origin: MultilocationEncoded,
+    }
+
+    /// How accounts of other chains are mapped to accounts on this chain.
+    /// Each mode derives the same account as the `xcm_builder` converter of the same name.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum AccountDerivation {
+        /// `blake2_256(("multiloc", location))`
+        Account32Hash,
+        /// `HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>`
+        HashedDescription,
     }
 
     #[ink::trait_definition]
@@ -51,24 +87,133 @@ mod xcm_handler {
         #[ink(message, selector = 0xd259f7ba)]
         fn get_address(&self, name: String) -> Option<VersionedMultiLocation>;
 
+        #[ink(message, selector = 0x44008217)]
+        fn get_address_for(
+            &self,
+            name: String,
+            chain: TargetChain,
+        ) -> Option<VersionedMultiLocation>;
+
+        #[ink(message, selector = 0xd97c3ecd)]
+        fn get_records(&self, name: String) -> Vec<(String, String)>;
+
+        #[ink(message, selector = 0x404f1d73)]
+        fn get_primary_name(&self, account: AccountId) -> Option<String>;
+
+        #[ink(message, selector = 0xf23d4b6c)]
+        fn get_price(&self, name: String, years: u8) -> Balance;
+
         #[ink(message, selector = 0x56c905c6)]
-        fn xcm_register_name(&mut self, caller: AccountId, name: String) -> Result<(), u8>;
+        fn xcm_register_name(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            years: u8,
+            paid: Balance,
+        ) -> Result<Balance, DomainServiceError>;
+
+        #[ink(message, selector = 0xd58d40dc)]
+        fn xcm_renew_name(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            years: u8,
+            paid: Balance,
+        ) -> Result<Balance, DomainServiceError>;
 
         #[ink(message, selector = 0xf874dc03)]
         fn xcm_transfer_name(
             &mut self,
             caller: AccountId,
+            origin: MultilocationEncoded,
             name: String,
             receiver: AccountId,
-        ) -> Result<(), u8>;
+        ) -> Result<(), DomainServiceError>;
 
         #[ink(message, selector = 0xa06e9770)]
         fn xcm_set_address(
             &mut self,
             caller: AccountId,
+            origin: MultilocationEncoded,
             name: String,
-            loc: MultilocationEncoded,
-        ) -> Result<(), u8>;
+            loc: VersionedMultilocationEncoded,
+        ) -> Result<(), DomainServiceError>;
+
+        #[ink(message, selector = 0x589fdf7b)]
+        fn xcm_set_address_for(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            chain: TargetChain,
+            loc: Option<VersionedMultilocationEncoded>,
+        ) -> Result<(), DomainServiceError>;
+
+        #[ink(message, selector = 0x6aa2f51f)]
+        fn xcm_set_records(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            records: Vec<(String, Option<String>)>,
+        ) -> Result<(), DomainServiceError>;
+
+        #[ink(message, selector = 0x3d7b9655)]
+        fn xcm_set_primary_name(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            location: MultilocationEncoded,
+            name: Option<String>,
+        ) -> Result<(), DomainServiceError>;
+
+        #[ink(message, selector = 0x2451d67e)]
+        fn xcm_set_subdomain_owner(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            label: String,
+            owner: AccountId,
+        ) -> Result<(), DomainServiceError>;
+
+        #[ink(message, selector = 0x97bc1a72)]
+        fn xcm_revoke_subdomain(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            label: String,
+        ) -> Result<(), DomainServiceError>;
+
+        #[ink(message, selector = 0x594eb640)]
+        fn xcm_lock_subdomain(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            label: String,
+        ) -> Result<(), DomainServiceError>;
+
+        #[ink(message, selector = 0x86689dd7)]
+        fn xcm_set_manager(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            name: String,
+            manager: Option<AccountId>,
+        ) -> Result<(), DomainServiceError>;
+
+        #[ink(message, selector = 0xbf4b9bb8)]
+        fn xcm_set_operator(
+            &mut self,
+            caller: AccountId,
+            origin: MultilocationEncoded,
+            operator: AccountId,
+            approved: bool,
+        ) -> Result<(), DomainServiceError>;
     }
 
     #[ink(storage)]
@@ -76,16 +221,43 @@ mod xcm_handler {
         admin: AccountId,
         domain_service: ink::contract_ref!(DomainService),
         xc_contracts: Mapping<AccountId, MultilocationEncoded>,
+        /// Location of the chain this contract is deployed on
+        universal_location: ChainLocation,
+        /// Runtime call indices of this chain, also used for the chains of xc-contracts
+        /// without a profile of their own
+        chain_profile: ChainProfile,
+        /// Runtime call indices of the chain hosting each xc-contract
+        xc_contract_profiles: Mapping<AccountId, ChainProfile>,
+        /// How responses pay for their execution on the chains of xc-contracts
+        fee_mode: XcmFeeMode,
+        /// Derivation of the accounts acting on behalf of callers from other chains
+        account_derivation: AccountDerivation,
+        /// Origin chains whose accounts are used as-is, without derivation
+        alias_chains: Mapping<ChainLocation, ()>,
+        /// Chains (besides this one & the relay chain) resolving addresses can point to
+        known_chains: Mapping<ChainLocation, ()>,
         custom_weight: Option<(u64, u64)>,
     }
 
     impl XcmHandler {
         #[ink(constructor)]
-        pub fn new(admin: AccountId, domain_service_addr: AccountId, custom_weight: Option<(u64, u64)>) -> Self {
+        pub fn new(
+            admin: AccountId,
+            domain_service_addr: AccountId,
+            universal_location: ChainLocation,
+            custom_weight: Option<(u64, u64)>,
+        ) -> Self {
             Self {
                 admin,
                 domain_service: domain_service_addr.into(),
                 xc_contracts: Mapping::default(),
+                universal_location,
+                chain_profile: ChainProfile::default(),
+                xc_contract_profiles: Mapping::default(),
+                fee_mode: XcmFeeMode::default(),
+                account_derivation: AccountDerivation::Account32Hash,
+                alias_chains: Mapping::default(),
+                known_chains: Mapping::default(),
                 custom_weight,
             }
         }
@@ -98,9 +270,150 @@ mod xcm_handler {
         ) -> Result<(), Error> {
             self.ensure_admin()?;
             self.xc_contracts.insert(xc_contract_soac, &origin_path);
+
+            let origin_chain = ChainLocation::new(origin_path.para_id);
+            self.known_chains.insert(origin_chain, &());
+            Ok(())
+        }
+
+        /// ADMIN use only! Allows (dis)allowing resolving addresses on the chain.
+        /// Chains of xc-contracts are known once added.
+        #[ink(message)]
+        pub fn set_known_chain(&mut self, chain: ChainLocation, known: bool) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if known {
+                self.known_chains.insert(chain, &());
+            } else {
+                self.known_chains.remove(chain);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_known_chain(&self, chain: ChainLocation) -> bool {
+            chain == self.universal_location
+                || chain.para_id.is_none()
+                || self.known_chains.contains(chain)
+        }
+
+        /// ADMIN use only! Allows updating the location of the chain this contract is deployed on
+        #[ink(message)]
+        pub fn set_universal_location(
+            &mut self,
+            universal_location: ChainLocation,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.universal_location = universal_location;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_universal_location(&self) -> ChainLocation {
+            self.universal_location
+        }
+
+        /// ADMIN use only! Allows updating the runtime call indices of this chain
+        #[ink(message)]
+        pub fn set_chain_profile(&mut self, chain_profile: ChainProfile) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.chain_profile = chain_profile;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_chain_profile(&self) -> ChainProfile {
+            self.chain_profile
+        }
+
+        /// ADMIN use only! Allows setting the runtime call indices of the chain hosting the xc-contract.
+        /// Pass `None` to fall back to this chain's profile.
+        #[ink(message)]
+        pub fn set_xc_contract_profile(
+            &mut self,
+            xc_contract_soac: AccountId,
+            profile: Option<ChainProfile>,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if let Some(profile) = profile {
+                self.xc_contract_profiles.insert(xc_contract_soac, &profile);
+            } else {
+                self.xc_contract_profiles.remove(xc_contract_soac);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_xc_contract_profile(&self, xc_contract_soac: AccountId) -> ChainProfile {
+            self.xc_contract_profiles
+                .get(xc_contract_soac)
+                .unwrap_or(self.chain_profile)
+        }
+
+        /// ADMIN use only! Allows choosing how responses pay for their execution.
+        /// Paid fees are withdrawn from this contract's account on the chain of the xc-contract.
+        #[ink(message)]
+        pub fn set_fee_mode(&mut self, fee_mode: XcmFeeMode) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.fee_mode = fee_mode;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_fee_mode(&self) -> XcmFeeMode {
+            self.fee_mode
+        }
+
+        /// ADMIN use only! Allows choosing how the accounts of callers from other chains are derived
+        #[ink(message)]
+        pub fn set_account_derivation(
+            &mut self,
+            account_derivation: AccountDerivation,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            self.account_derivation = account_derivation;
             Ok(())
         }
 
+        #[ink(message)]
+        pub fn get_account_derivation(&self) -> AccountDerivation {
+            self.account_derivation
+        }
+
+        /// ADMIN use only! Allows using the accounts of callers from the origin chain as-is.
+        /// Only enable it for chains sharing the key scheme & account ownership with this chain.
+        #[ink(message)]
+        pub fn set_alias_mode(
+            &mut self,
+            origin_chain: ChainLocation,
+            enabled: bool,
+        ) -> Result<(), Error> {
+            self.ensure_admin()?;
+            if enabled {
+                self.alias_chains.insert(origin_chain, &());
+            } else {
+                self.alias_chains.remove(origin_chain);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_alias_mode(&self, origin_chain: ChainLocation) -> bool {
+            self.alias_chains.contains(origin_chain)
+        }
+
+        /// Returns the account acting on behalf of the `account` calling via the xc-contract
+        #[ink(message)]
+        pub fn get_interchain_account(
+            &self,
+            xc_contract_soac: AccountId,
+            account: AccountId,
+        ) -> Result<AccountId, Error> {
+            let Some(origin_path) = self.xc_contracts.get(xc_contract_soac) else {
+                return Err(Error::UnknownCaller);
+            };
+            self.interchain_account(&origin_path, &account)
+        }
+
         #[ink(message)]
         pub fn get_admin(&self) -> AccountId {
             self.admin
@@ -111,6 +424,19 @@ mod xcm_handler {
             self.xc_contracts.get(addr)
         }
 
+        /// Returns the address the name resolves to for a request of the xc-contract
+        /// at `origin_path`, re-anchored for its chain, i.e. as sent in the response
+        #[ink(message)]
+        pub fn get_address_for_origin(
+            &self,
+            name: String,
+            origin_path: MultilocationEncoded,
+        ) -> Result<Option<VersionedMultiLocation>, Error> {
+            self.resolve_address(name, &origin_path)
+                .map(|rs| self.reanchor_loc(&rs, &origin_path))
+                .transpose()
+        }
+
         #[ink(message)]
         pub fn get_state_manager(&self) -> AccountId {
             use ink::ToAccountId;
@@ -127,7 +453,7 @@ mod xcm_handler {
 
             let output = self.domain_service.get_owner(name);
             let read_interface = ReadInterface::Owner(output);
-            self.send_response_back(&origin_path, &tid, &read_interface)?;
+            self.send_response_back(&origin_path, &tid, Operation::GetOwner, &read_interface)?;
 
             Ok(output)
         }
@@ -140,69 +466,487 @@ mod xcm_handler {
         ) -> Result<Option<VersionedMultiLocation>, Error> {
             let origin_path = self.auth_caller()?;
 
-            let output = self.domain_service.get_address(name);
+            let output = self.resolve_address(name, &origin_path);
 
-            let re_anchored_loc = match &output {
-                Some(rs) => Some(self.reanchor_loc(rs, &origin_path)?),
-                None => None,
+            // Failures are reported rather than returned, as reverting would discard the response
+            let re_anchored_loc = output
+                .as_ref()
+                .map(|rs| self.reanchor_loc(rs, &origin_path))
+                .transpose();
+
+            let read_interface = match re_anchored_loc {
+                Ok(loc) => ReadInterface::Address(loc),
+                Err(e) => ReadInterface::Failed(e),
             };
+            self.send_response_back(&origin_path, &tid, Operation::GetAddress, &read_interface)?;
 
-            let read_interface = ReadInterface::Address(re_anchored_loc);
-            self.send_response_back(&origin_path, &tid, &read_interface)?;
+            Ok(output)
+        }
+
+        /// Resolves the owners of all the names, answered with a single response
+        #[ink(message)]
+        pub fn get_owners(
+            &mut self,
+            tid: TicketId,
+            names: Vec<String>,
+        ) -> Result<Vec<Option<AccountId>>, Error> {
+            let origin_path = self.auth_caller()?;
+            Self::ensure_batch_size(&names)?;
+
+            let output: Vec<_> = names
+                .into_iter()
+                .map(|name| self.domain_service.get_owner(name))
+                .collect();
+
+            let read_interface = ReadInterface::Owners(output.clone());
+            self.send_response_back(&origin_path, &tid, Operation::GetOwners, &read_interface)?;
 
             Ok(output)
         }
 
+        /// Resolves the addresses of all the names, answered with a single response.
+        /// Addresses that can't be re-anchored for the xc-contract are reported as `None`.
         #[ink(message)]
-        pub fn register_name(&mut self, caller: AccountId, name: String) -> Result<(), Error> {
+        pub fn get_addresses(
+            &mut self,
+            tid: TicketId,
+            names: Vec<String>,
+        ) -> Result<Vec<Option<VersionedMultiLocation>>, Error> {
             let origin_path = self.auth_caller()?;
+            Self::ensure_batch_size(&names)?;
 
-            let caller_soac = self.interchain_account(&origin_path, &caller);
-            self.domain_service
-                .xcm_register_name(caller_soac, name)
-                .map_err(Error::DomainService)
+            let mut output = Vec::with_capacity(names.len());
+            let mut re_anchored_locs = Vec::with_capacity(names.len());
+            for name in names {
+                let loc = self.resolve_address(name, &origin_path);
+                let re_anchored_loc = loc
+                    .as_ref()
+                    .and_then(|rs| self.reanchor_loc(rs, &origin_path).ok());
+                output.push(loc);
+                re_anchored_locs.push(re_anchored_loc);
+            }
+
+            let read_interface = ReadInterface::Addresses(re_anchored_locs);
+            self.send_response_back(&origin_path, &tid, Operation::GetAddresses, &read_interface)?;
+
+            Ok(output)
+        }
+
+        #[ink(message)]
+        pub fn get_price(
+            &mut self,
+            tid: TicketId,
+            name: String,
+            years: u8,
+        ) -> Result<Balance, Error> {
+            let origin_path = self.auth_caller()?;
+
+            let output = self.domain_service.get_price(name, years);
+            let read_interface = ReadInterface::Price(output);
+            self.send_response_back(&origin_path, &tid, Operation::GetPrice, &read_interface)?;
+
+            Ok(output)
+        }
+
+        #[ink(message)]
+        pub fn get_records(
+            &mut self,
+            tid: TicketId,
+            name: String,
+        ) -> Result<Vec<(String, String)>, Error> {
+            let origin_path = self.auth_caller()?;
+
+            let output = self.domain_service.get_records(name);
+            let read_interface = ReadInterface::Records(output.clone());
+            self.send_response_back(&origin_path, &tid, Operation::GetRecords, &read_interface)?;
+
+            Ok(output)
+        }
+
+        /// Resolves the primary name of `account` on the chain of the xc-contract
+        #[ink(message)]
+        pub fn get_name(
+            &mut self,
+            tid: TicketId,
+            account: AccountId,
+        ) -> Result<Option<String>, Error> {
+            let origin_path = self.auth_caller()?;
+
+            let output = self
+                .interchain_account(&origin_path, &account)
+                .ok()
+                .and_then(|account_soac| self.domain_service.get_primary_name(account_soac));
+
+            let read_interface = ReadInterface::Name(output.clone());
+            self.send_response_back(&origin_path, &tid, Operation::GetName, &read_interface)?;
+
+            Ok(output)
+        }
+
+        /** Write operations STARTS here */
+        // The outcome of each write is sent back to the xc-contract as `ReadInterface::Write`.
+        // Failures are reported rather than returned, as reverting would discard the response.
+
+        #[ink(message)]
+        pub fn register_name(
+            &mut self,
+            tid: TicketId,
+            caller: AccountId,
+            name: String,
+            years: u8,
+            paid: Balance,
+        ) -> Result<(), Error> {
+            let origin_path = self.auth_caller()?;
+
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    self.domain_service
+                        .xcm_register_name(caller_soac, origin_path, name, years, paid)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::RegisterName,
+                &ReadInterface::Write(result),
+            )
+        }
+
+        #[ink(message)]
+        pub fn renew_name(
+            &mut self,
+            tid: TicketId,
+            caller: AccountId,
+            name: String,
+            years: u8,
+            paid: Balance,
+        ) -> Result<(), Error> {
+            let origin_path = self.auth_caller()?;
+
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    self.domain_service
+                        .xcm_renew_name(caller_soac, origin_path, name, years, paid)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::RenewName,
+                &ReadInterface::Write(result),
+            )
         }
 
         #[ink(message)]
         pub fn transfer_name(
             &mut self,
+            tid: TicketId,
             caller: AccountId,
             name: String,
             receiver: AccountId,
         ) -> Result<(), Error> {
             let origin_path = self.auth_caller()?;
 
-            let caller_soac = self.interchain_account(&origin_path, &caller);
-            self.domain_service
-                .xcm_transfer_name(caller_soac, name, receiver)
-                .map_err(Error::DomainService)
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    self.domain_service
+                        .xcm_transfer_name(caller_soac, origin_path, name, receiver)
+                        .map(|_| 0)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::TransferName,
+                &ReadInterface::Write(result),
+            )
         }
 
         #[ink(message)]
         pub fn set_address(
             &mut self,
+            tid: TicketId,
             caller: AccountId,
             name: String,
-            loc: MultilocationEncoded,
+            loc: VersionedMultilocationEncoded,
         ) -> Result<(), Error> {
             let origin_path = self.auth_caller()?;
-            let caller_soac = self.interchain_account(&origin_path, &caller);
 
-            // Re-anchor loc w.r.t our context
-            let loc = self.reanchor_loc2(&loc, &origin_path)?;
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    // Re-anchor loc w.r.t our context
+                    let loc = self.reanchor_loc2(&loc, &origin_path)?;
 
-            self.domain_service
-                .xcm_set_address(caller_soac, name, loc)
-                .map_err(Error::DomainService)
+                    self.domain_service
+                        .xcm_set_address(caller_soac, origin_path, name, loc)
+                        .map(|_| 0)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::SetAddress,
+                &ReadInterface::Write(result),
+            )
+        }
+
+        /// Sets the address record of the name for the given chain, `None` removes it
+        #[ink(message)]
+        pub fn set_address_for(
+            &mut self,
+            tid: TicketId,
+            caller: AccountId,
+            name: String,
+            chain: TargetChain,
+            loc: Option<VersionedMultilocationEncoded>,
+        ) -> Result<(), Error> {
+            let origin_path = self.auth_caller()?;
+
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    // Re-anchor loc w.r.t our context
+                    let loc = match loc {
+                        Some(loc) => Some(self.reanchor_loc2(&loc, &origin_path)?),
+                        None => None,
+                    };
+
+                    self.domain_service
+                        .xcm_set_address_for(caller_soac, origin_path, name, chain, loc)
+                        .map(|_| 0)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::SetAddressFor,
+                &ReadInterface::Write(result),
+            )
+        }
+
+        #[ink(message)]
+        pub fn set_records(
+            &mut self,
+            tid: TicketId,
+            caller: AccountId,
+            name: String,
+            records: Vec<(String, Option<String>)>,
+        ) -> Result<(), Error> {
+            let origin_path = self.auth_caller()?;
+
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    self.domain_service
+                        .xcm_set_records(caller_soac, origin_path, name, records)
+                        .map(|_| 0)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::SetRecords,
+                &ReadInterface::Write(result),
+            )
+        }
+
+        #[ink(message)]
+        pub fn set_primary_name(
+            &mut self,
+            tid: TicketId,
+            caller: AccountId,
+            name: Option<String>,
+        ) -> Result<(), Error> {
+            let origin_path = self.auth_caller()?;
+
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    let location =
+                        MultilocationEncoded::new(origin_path.parents, origin_path.para_id, caller);
+                    self.domain_service
+                        .xcm_set_primary_name(caller_soac, origin_path, location, name)
+                        .map(|_| 0)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::SetPrimaryName,
+                &ReadInterface::Write(result),
+            )
+        }
+
+        #[ink(message)]
+        pub fn set_subdomain_owner(
+            &mut self,
+            tid: TicketId,
+            caller: AccountId,
+            name: String,
+            label: String,
+            owner: AccountId,
+        ) -> Result<(), Error> {
+            let origin_path = self.auth_caller()?;
+
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    self.domain_service
+                        .xcm_set_subdomain_owner(caller_soac, origin_path, name, label, owner)
+                        .map(|_| 0)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::SetSubdomainOwner,
+                &ReadInterface::Write(result),
+            )
+        }
+
+        #[ink(message)]
+        pub fn revoke_subdomain(
+            &mut self,
+            tid: TicketId,
+            caller: AccountId,
+            name: String,
+            label: String,
+        ) -> Result<(), Error> {
+            let origin_path = self.auth_caller()?;
+
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    self.domain_service
+                        .xcm_revoke_subdomain(caller_soac, origin_path, name, label)
+                        .map(|_| 0)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::RevokeSubdomain,
+                &ReadInterface::Write(result),
+            )
         }
 
+        #[ink(message)]
+        pub fn lock_subdomain(
+            &mut self,
+            tid: TicketId,
+            caller: AccountId,
+            name: String,
+            label: String,
+        ) -> Result<(), Error> {
+            let origin_path = self.auth_caller()?;
+
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    self.domain_service
+                        .xcm_lock_subdomain(caller_soac, origin_path, name, label)
+                        .map(|_| 0)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::LockSubdomain,
+                &ReadInterface::Write(result),
+            )
+        }
+
+        /// `manager` is an account on this chain, see `get_interchain_account`
+        #[ink(message)]
+        pub fn set_manager(
+            &mut self,
+            tid: TicketId,
+            caller: AccountId,
+            name: String,
+            manager: Option<AccountId>,
+        ) -> Result<(), Error> {
+            let origin_path = self.auth_caller()?;
+
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    self.domain_service
+                        .xcm_set_manager(caller_soac, origin_path, name, manager)
+                        .map(|_| 0)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::SetManager,
+                &ReadInterface::Write(result),
+            )
+        }
+
+        /// `operator` is an account on this chain, see `get_interchain_account`
+        #[ink(message)]
+        pub fn set_operator(
+            &mut self,
+            tid: TicketId,
+            caller: AccountId,
+            operator: AccountId,
+            approved: bool,
+        ) -> Result<(), Error> {
+            let origin_path = self.auth_caller()?;
+
+            let result = self
+                .interchain_account(&origin_path, &caller)
+                .and_then(|caller_soac| {
+                    self.domain_service
+                        .xcm_set_operator(caller_soac, origin_path, operator, approved)
+                        .map(|_| 0)
+                        .map_err(Error::DomainService)
+                });
+
+            self.send_response_back(
+                &origin_path,
+                &tid,
+                Operation::SetOperator,
+                &ReadInterface::Write(result),
+            )
+        }
+
+        /** Write operations ENDS here */
+
         fn send_response_back(
             &mut self,
             location: &MultilocationEncoded,
             tid: &TicketId,
+            op: Operation,
             read_interface: &ReadInterface,
         ) -> Result<(), Error> {
-            let path_to_chain = location.path_to_chain();
+            let result = match read_interface {
+                ReadInterface::Write(result) => Some(*result),
+                ReadInterface::Failed(e) => Some(Err(*e)),
+                _ => None,
+            };
+            self.env().emit_event(RequestHandled {
+                ticket_id: *tid,
+                origin: *location,
+                op,
+                result,
+            });
+
             let contract_address = location.account();
 
             let selector = ink::selector_bytes!("accept_response");
@@ -210,14 +954,49 @@ mod xcm_handler {
 
             let wt = self.custom_weight.map(|(x,y)| utils::Weight::from_parts(x,y));
 
+            let here = &self.universal_location;
+            let origin_chain = ChainLocation::new(location.para_id);
+            let destination = Destination {
+                path: location.path_to_chain(),
+                // Responses are always sent back to the authenticated caller
+                profile: self.get_xc_contract_profile(self.env().caller()),
+                fee: self
+                    .fee_mode
+                    .fee_for(self.env().account_id(), here, &origin_chain),
+            };
+
             make_xcm_contract_call::<Self>(
-                path_to_chain.into(),
+                &self.chain_profile,
+                destination,
                 contract_address,
                 (selector, tid, encoded_response).encode(),
                 0,
                 wt,
-            )
-            .map_err(Into::into)
+            )?;
+
+            self.env().emit_event(ResponseDispatched {
+                ticket_id: *tid,
+                origin: *location,
+            });
+            Ok(())
+        }
+
+        // Picks the address record for the chain of the xc-contract, falling back to the
+        // default address of the name
+        fn resolve_address(
+            &self,
+            name: String,
+            origin_path: &MultilocationEncoded,
+        ) -> Option<VersionedMultiLocation> {
+            // Only an xc-contract deployed here shares the records of this chain, a child
+            // parachain of a relay-chain hub is reached as `(0, Some(id))`
+            let chain = match (origin_path.parents, origin_path.para_id) {
+                (0, None) => self.universal_location,
+                (_, para_id) => ChainLocation::new(para_id),
+            };
+            self.domain_service
+                .get_address_for(name.clone(), chain.into())
+                .or_else(|| self.domain_service.get_address(name))
         }
 
         // Re-anchors `loc` from Here (context) to destination chain location (target)
@@ -227,7 +1006,7 @@ mod xcm_handler {
             relative_to: &MultilocationEncoded,
         ) -> Result<VersionedMultiLocation, Error> {
             let target = relative_to.path_to_chain();
-            let context = X1(Parachain(1));
+            let context = self.universal_location.interior();
 
             let Ok(mut loc): Result<MultiLocation, _> = loc.clone().try_into() else {
                 return Err(Error::UnsupportedXcmVersion);
@@ -242,44 +1021,98 @@ mod xcm_handler {
         // Re-anchors custom-encoded Multilocation coming from secondary chain (context) to Here (target) in custom-encoded format
         fn reanchor_loc2(
             &self,
-            loc: &MultilocationEncoded,
+            loc: &VersionedMultilocationEncoded,
             origin_path: &MultilocationEncoded,
-        ) -> Result<MultilocationEncoded, Error> {
+        ) -> Result<VersionedMultilocationEncoded, Error> {
             let get_inverted_target = |here: Junctions, origin_path: &MultilocationEncoded| {
                 let destination = origin_path.path_to_chain();
                 here.invert_target(&destination)
-                    .map_err(|_| Error::UnsupportedMultiLocationFormat)
+                    .map_err(|_| Error::ReanchoringFailed)
             };
 
             // 1. Retrieve context info of the origin
             let context = origin_path.get_context();
 
             // 2. Invert `here` w.r.t to origin path's context
-            let here = X1(Parachain(1));
+            let here = self.universal_location.interior();
             let target = get_inverted_target(here, origin_path)?;
 
-            // 3. Convert MultilocationEncoded to MultiLocation
-            let mut loc_ml: MultiLocation = loc.into();
+            // 3. Convert VersionedMultilocationEncoded to MultiLocation
+            let mut loc_ml: MultiLocation = loc
+                .clone()
+                .try_into()
+                .map_err(|_| Error::UnsupportedMultiLocationFormat)?;
 
             // 4. Reanchor loc to our context `here`
-            loc_ml.reanchor(&target, context).unwrap();
+            loc_ml
+                .reanchor(&target, context)
+                .map_err(|_| Error::ReanchoringFailed)?;
 
-            // 5. Encode the anchored MultiLocation back to MultilocationEncoded format
-            let new_loc = loc_ml
+            // 5. Make sure it stays on a known chain of the relay-chain network,
+            // or points to another consensus system
+            if !self.is_known_location(&loc_ml) {
+                return Err(Error::ReanchoringFailed);
+            }
+
+            // 6. Encode the anchored MultiLocation back to the custom format
+            loc_ml
                 .try_into()
-                .map_err(|_| Error::UnsupportedMultiLocationFormat)?;
+                .map_err(|_| Error::UnsupportedMultiLocationFormat)
+        }
 
-            Ok(new_loc)
+        // Checks if the location (relative to here) points to a known chain, or beyond
+        // the relay-chain network through a `GlobalConsensus` junction
+        fn is_known_location(&self, loc: &MultiLocation) -> bool {
+            let depth = self.universal_location.interior().len() as u8;
+
+            match (loc.parents, loc.first_interior()) {
+                // Parachains are children of the relay chain only
+                (0, Some(Parachain(_))) if depth > 0 => false,
+                (parents, Some(Parachain(id))) if parents == depth => {
+                    self.is_known_chain(ChainLocation::new(Some(*id)))
+                }
+                (0, _) => true,
+                (parents, _) if parents == depth => self.is_known_chain(ChainLocation::new(None)),
+                (parents, Some(GlobalConsensus(_))) => parents == depth + 1,
+                _ => false,
+            }
         }
 
-        // Gives us the control to have our own form of interchain account
-        // Ideally should comply w/ the chains' Sovereign Account for consistency
+        // Derives the account of `origin` calling from the chain of the xc-contract,
+        // complying w/ the chains' Sovereign Account for consistency
         fn interchain_account(
             &self,
-            _origin_path: &MultilocationEncoded,
+            origin_path: &MultilocationEncoded,
             origin: &AccountId,
-        ) -> AccountId {
-            *origin // Alias Mode ON
+        ) -> Result<AccountId, Error> {
+            let origin_chain = ChainLocation::new(origin_path.para_id);
+            if self.alias_chains.contains(origin_chain) {
+                return Ok(*origin);
+            }
+
+            let location =
+                MultilocationEncoded::new(origin_path.parents, origin_path.para_id, *origin);
+
+            let hash = match self.account_derivation {
+                AccountDerivation::Account32Hash => {
+                    let location: MultiLocation = location.into();
+                    self.env()
+                        .hash_encoded::<Blake2x256, _>(&("multiloc", location))
+                }
+                AccountDerivation::HashedDescription => {
+                    let id: &[u8; 32] = origin.as_ref();
+                    let terminal = (b"AccountId32", id).encode();
+                    let description = match (location.parents, location.para_id) {
+                        (0, Some(id)) => (b"ChildChain", Compact(id), terminal).encode(),
+                        (1, Some(id)) => (b"SiblingChain", Compact(id), terminal).encode(),
+                        (1, None) => (b"ParentChain", terminal).encode(),
+                        _ => return Err(Error::UnsupportedMultiLocationFormat),
+                    };
+                    self.env().hash_bytes::<Blake2x256>(&description)
+                }
+            };
+
+            Ok(AccountId::from(hash))
         }
 
         fn auth_caller(&self) -> Result<MultilocationEncoded, Error> {
@@ -291,6 +1124,13 @@ mod xcm_handler {
             }
         }
 
+        fn ensure_batch_size(names: &[String]) -> Result<(), Error> {
+            if names.is_empty() || names.len() > MAX_BATCH_SIZE {
+                Err(Error::InvalidBatchSize)?;
+            }
+            Ok(())
+        }
+
         fn ensure_admin(&self) -> Result<(), Error> {
             if self.env().caller() != self.admin {
                 Err(Error::NotAdmin)?;
@@ -143,6 +143,7 @@ pub type RelayChainPalletXcm = pallet_xcm::Pallet<relay_chain::Runtime>;
 pub type ParachainPalletXcm = pallet_xcm::Pallet<parachain::Runtime>;
 pub type ParachainContracts = pallet_contracts::Pallet<parachain::Runtime>;
 pub type ParachainBalances = pallet_balances::Pallet<parachain::Runtime>;
+pub type RelayChainBalances = pallet_balances::Pallet<relay_chain::Runtime>;
 
 #[cfg(test)]
 mod tests {
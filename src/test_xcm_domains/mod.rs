@@ -15,6 +15,102 @@ mod xcm_dns;
 
 pub const TX_GAS: u64 = 10_000_000_000;
 
+/// Mirrors `utils::VersionedMultilocationEncoded`
+#[derive(Encode)]
+pub enum Address {
+    /// (parents, para_id, account)
+    V1(u8, Option<u32>, AccountId32),
+    /// (parents, interior)
+    V2(u8, Vec<Junction>),
+}
+
+/// Mirrors `utils::TargetChain`
+#[derive(Debug, PartialEq, Encode, Decode, Clone, Copy)]
+pub enum TargetChain {
+    Relay,
+    Parachain(u32),
+    GlobalConsensus(NetworkId),
+}
+
+/// Mirrors `utils::FeeEstimator`: (weight_per_instruction, units_per_second, units_per_mb)
+pub type FeeEstimator = ((u64, u64), u128, u128);
+
+/// Mirrors the PSP34 `Id` of `domain_service`
+#[derive(Debug, PartialEq, Encode, Decode, Clone)]
+pub enum Id {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Bytes(Vec<u8>),
+}
+
+/// Mirrors the `PSP34Error` of `domain_service`
+#[derive(Debug, PartialEq, Encode, Decode, Clone)]
+pub enum PSP34Error {
+    Custom(String),
+    SelfApprove,
+    NotApproved,
+    TokenExists,
+    TokenNotExists,
+    SafeTransferCheckFailed(String),
+}
+
+/// Mirrors `utils::MultilocationEncoded`: (parents, para_id, addr)
+pub type MultilocationEncoded = (u8, Option<u32>, AccountId32);
+
+/// Mirrors the events of `domain_service` checked by the tests
+#[derive(Debug, PartialEq, Decode)]
+pub enum DomainServiceEvent {
+    #[codec(index = 0)]
+    NameRegistered {
+        name_hash: [u8; 32],
+        name: String,
+        owner: AccountId32,
+        expiry: u32,
+        price: Balance,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    },
+    #[codec(index = 2)]
+    NameTransferred {
+        name_hash: [u8; 32],
+        name: String,
+        from: AccountId32,
+        to: AccountId32,
+        via_xcm: bool,
+        origin: Option<MultilocationEncoded>,
+    },
+    #[codec(index = 18)]
+    Transfer {
+        from: Option<AccountId32>,
+        to: Option<AccountId32>,
+        id: Id,
+    },
+    #[codec(index = 19)]
+    Approval {
+        owner: AccountId32,
+        operator: AccountId32,
+        id: Option<Id>,
+        approved: bool,
+    },
+}
+
+/// Returns the events emitted by the contract that decode as `E`, oldest first
+pub fn contract_events<E: Decode>(contract: &AccountId32) -> Vec<E> {
+    parachain::System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            parachain::RuntimeEvent::Contracts(pallet_contracts::Event::ContractEmitted {
+                contract: emitter,
+                data,
+            }) if &emitter == contract => E::decode(&mut &data[..]).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
 pub fn get_selector(name: &str) -> [u8; 4] {
     let bytes = sp_core::blake2_256(name.as_bytes());
     [bytes[0], bytes[1], bytes[2], bytes[3]]
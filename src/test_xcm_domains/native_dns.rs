@@ -6,7 +6,15 @@ pub fn register_name(
     name: &str,
 ) -> Result<(), u8> {
     let sel_register_name = get_selector("register_name");
-    let payload = (sel_register_name, name).encode();
+    let payload = (sel_register_name, name, 1u8).encode(); // (selector, name, years)
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 100);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn renew_name(state_manager: &AccountId32, caller: AccountId32, name: &str) -> Result<(), u8> {
+    let sel_renew_name = get_selector("renew_name");
+    let payload = (sel_renew_name, name, 1u8).encode(); // (selector, name, years)
 
     let encoded_resp = call_contract(&state_manager, caller, payload, 100);
     Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
@@ -29,7 +37,7 @@ pub fn set_address(
     state_manager: &AccountId32,
     caller: AccountId32,
     name: &str,
-    address: &(u8, Option<u32>, AccountId32),
+    address: &Address,
 ) -> Result<(), u8> {
     let sel_set_address = get_selector("set_address");
     let payload = (sel_set_address, name, address).encode();
@@ -54,6 +62,351 @@ pub fn get_address(state_manager: &AccountId32, name: &str) -> Option<VersionedM
     Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
 }
 
+pub fn set_address_for(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    chain: TargetChain,
+    address: Option<&Address>,
+) -> Result<(), u8> {
+    let sel_set_address_for = get_selector("set_address_for");
+    let payload = (sel_set_address_for, name, chain, address).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_address_for(
+    state_manager: &AccountId32,
+    name: &str,
+    chain: TargetChain,
+) -> Option<VersionedMultiLocation> {
+    let sel_get_address_for = get_selector("get_address_for");
+    let payload = (sel_get_address_for, name, chain).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_all_addresses(
+    state_manager: &AccountId32,
+    name: &str,
+) -> Vec<(TargetChain, VersionedMultiLocation)> {
+    let sel_get_all_addresses = get_selector("get_all_addresses");
+    let payload = (sel_get_all_addresses, name).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_records(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    records: &[(&str, Option<&str>)],
+) -> Result<(), u8> {
+    let sel_set_records = get_selector("set_records");
+    let payload = (sel_set_records, name, records).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_records(state_manager: &AccountId32, name: &str) -> Vec<(String, String)> {
+    let sel_get_records = get_selector("get_records");
+    let payload = (sel_get_records, name).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_record(state_manager: &AccountId32, name: &str, key: &str) -> Option<String> {
+    let sel_get_record = get_selector("get_record");
+    let payload = (sel_get_record, name, key).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_subdomain_owner(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    label: &str,
+    owner: AccountId32,
+) -> Result<(), u8> {
+    let sel_set_subdomain_owner = get_selector("set_subdomain_owner");
+    let payload = (sel_set_subdomain_owner, name, label, owner).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn revoke_subdomain(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    label: &str,
+) -> Result<(), u8> {
+    let sel_revoke_subdomain = get_selector("revoke_subdomain");
+    let payload = (sel_revoke_subdomain, name, label).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn lock_subdomain(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    label: &str,
+) -> Result<(), u8> {
+    let sel_lock_subdomain = get_selector("lock_subdomain");
+    let payload = (sel_lock_subdomain, name, label).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_subdomains(state_manager: &AccountId32, name: &str) -> Vec<String> {
+    let sel_get_subdomains = get_selector("get_subdomains");
+    let payload = (sel_get_subdomains, name).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_primary_name(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    name: Option<&str>,
+) -> Result<(), u8> {
+    let sel_set_primary_name = get_selector("set_primary_name");
+    let payload = (sel_set_primary_name, name).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_primary_name(state_manager: &AccountId32, account: AccountId32) -> Option<String> {
+    let sel_get_primary_name = get_selector("get_primary_name");
+    let payload = (sel_get_primary_name, account).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_expiry(state_manager: &AccountId32, name: &str) -> Option<u32> {
+    let sel_get_expiry = get_selector("get_expiry");
+    let payload = (sel_get_expiry, name).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_price(state_manager: &AccountId32, name: &str, years: u8) -> Balance {
+    let sel_get_price = get_selector("get_price");
+    let payload = (sel_get_price, name, years).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_price_config(
+    state_manager: &AccountId32,
+    price_tiers: Vec<Balance>,
+    year_multipliers: Vec<u32>,
+) {
+    let sel_set_price_tiers = get_selector("set_price_tiers");
+    let payload = (sel_set_price_tiers, price_tiers).encode();
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
+    assert_eq!(resp, Ok(()));
+
+    let sel_set_year_multipliers = get_selector("set_year_multipliers");
+    let payload = (sel_set_year_multipliers, year_multipliers).encode();
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
+    assert_eq!(resp, Ok(()));
+}
+
+pub fn set_periods(state_manager: &AccountId32, registration_period: u32, grace_period: u32) {
+    let sel_set_periods = get_selector("set_periods");
+    let payload = (sel_set_periods, registration_period, grace_period).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
+
+    assert_eq!(resp, Ok(()));
+}
+
+pub fn set_manager(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    manager: Option<AccountId32>,
+) -> Result<(), u8> {
+    let sel_set_manager = get_selector("set_manager");
+    let payload = (sel_set_manager, name, manager).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_operator(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    operator: AccountId32,
+    approved: bool,
+) -> Result<(), u8> {
+    let sel_set_operator = get_selector("set_operator");
+    let payload = (sel_set_operator, operator, approved).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_manager(state_manager: &AccountId32, name: &str) -> Option<AccountId32> {
+    let sel_get_manager = get_selector("get_manager");
+    let payload = (sel_get_manager, name).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn token_id(name: &str) -> Id {
+    Id::Bytes(name.as_bytes().to_vec())
+}
+
+pub fn psp34_owner_of(state_manager: &AccountId32, name: &str) -> Option<AccountId32> {
+    let sel_owner_of = get_selector("PSP34::owner_of");
+    let payload = (sel_owner_of, token_id(name)).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn psp34_balance_of(state_manager: &AccountId32, owner: AccountId32) -> u32 {
+    let sel_balance_of = get_selector("PSP34::balance_of");
+    let payload = (sel_balance_of, owner).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn psp34_approve(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    operator: AccountId32,
+    name: Option<&str>,
+    approved: bool,
+) -> Result<(), PSP34Error> {
+    let sel_approve = get_selector("PSP34::approve");
+    let payload = (sel_approve, operator, name.map(token_id), approved).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn psp34_transfer(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    to: AccountId32,
+    name: &str,
+) -> Result<(), PSP34Error> {
+    let sel_transfer = get_selector("PSP34::transfer");
+    let payload = (sel_transfer, to, token_id(name), Vec::<u8>::new()).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn psp34_token_by_index(state_manager: &AccountId32, index: u128) -> Result<Id, PSP34Error> {
+    let sel_token_by_index = get_selector("PSP34Enumerable::token_by_index");
+    let payload = (sel_token_by_index, index).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn psp34_owners_token_by_index(
+    state_manager: &AccountId32,
+    owner: AccountId32,
+    index: u128,
+) -> Result<Id, PSP34Error> {
+    let sel_owners_token_by_index = get_selector("PSP34Enumerable::owners_token_by_index");
+    let payload = (sel_owners_token_by_index, owner, index).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn psp34_get_attribute(state_manager: &AccountId32, name: &str, key: &str) -> Option<Vec<u8>> {
+    let sel_get_attribute = get_selector("PSP34Metadata::get_attribute");
+    let payload = (sel_get_attribute, token_id(name), key.as_bytes().to_vec()).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+/// Mirrors `utils::NameRules`: (min_length, max_length, extra_chars, allow_unicode)
+pub type NameRules = (u32, u32, String, bool);
+
+pub fn set_name_rules(state_manager: &AccountId32, name_rules: NameRules) {
+    let sel_set_name_rules = get_selector("set_name_rules");
+    let payload = (sel_set_name_rules, name_rules).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
+
+    assert_eq!(resp, Ok(()));
+}
+
+pub fn register_name_with_referrer(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    referrer: AccountId32,
+) -> Result<(), u8> {
+    let sel_register_name = get_selector("register_name_with_referrer");
+    let payload = (sel_register_name, name, 1u8, referrer).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 100);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+/// Mirrors `utils::FeeSplit`: (treasury, referrer, locked)
+pub type FeeSplit = (u8, u8, u8);
+
+pub fn set_fee_split(state_manager: &AccountId32, fee_split: FeeSplit) -> Result<(), u8> {
+    let sel_set_fee_split = get_selector("set_fee_split");
+    let payload = (sel_set_fee_split, fee_split).encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_withdrawable_balance(state_manager: &AccountId32) -> Balance {
+    let sel_get_withdrawable_balance = get_selector("get_withdrawable_balance");
+    let payload = sel_get_withdrawable_balance.encode();
+
+    let encoded_resp = call_contract(&state_manager, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn withdraw(
+    state_manager: &AccountId32,
+    caller: AccountId32,
+    amount: Balance,
+    to: AccountId32,
+) -> Result<(), u8> {
+    let sel_withdraw = get_selector("withdraw");
+    let payload = (sel_withdraw, amount, to).encode();
+
+    let encoded_resp = call_contract(&state_manager, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
 #[test]
 fn registration_works() {
     MockNet::reset();
@@ -74,6 +427,39 @@ fn registration_works() {
     });
 }
 
+#[test]
+fn names_are_normalized_and_validated() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+
+    ParaA::execute_with(|| {
+        setup::fund_address(&BOB);
+
+        // Names are lowercased, fullwidth forms are folded to ASCII
+        let rs = register_name(&state_manager, ALICE, "ＡＬＩＣＥ");
+        assert_eq!(rs, Ok(()));
+        assert_eq!(get_owner(&state_manager, "alice"), Some(ALICE));
+        assert_eq!(get_owner(&state_manager, "Alice"), Some(ALICE));
+
+        // Case variants are the same name
+        let rs = register_name(&state_manager, BOB, "ALICE");
+        assert!(rs.is_err());
+
+        // Empty names, whitespace & homoglyphs are rejected
+        for name in ["", "al ice", "\u{0430}lice", "alice!"] {
+            let rs = register_name(&state_manager, BOB, name);
+            assert!(rs.is_err());
+        }
+
+        // Rules are configurable by the admin
+        set_name_rules(&state_manager, (3, 64, "-_".to_string(), false));
+        let rs = register_name(&state_manager, BOB, "bo");
+        assert!(rs.is_err());
+        let rs = register_name(&state_manager, BOB, "bob_1");
+        assert_eq!(rs, Ok(()));
+    });
+}
+
 #[test]
 fn set_address_works() {
     MockNet::reset();
@@ -84,7 +470,7 @@ fn set_address_works() {
         register_name(&state_manager, ALICE, "alice").unwrap();
 
         // Set domain's resolving MultiLocation address
-        let address = (0, None, ALICE);
+        let address = Address::V1(0, None, ALICE);
         let rs = set_address(&state_manager, ALICE, "alice", &address);
         assert_eq!(rs, Ok(()));
 
@@ -100,6 +486,119 @@ fn set_address_works() {
     });
 }
 
+#[test]
+fn address_records_work() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+
+    let para_b = TargetChain::Parachain(2);
+    let ethereum = TargetChain::GlobalConsensus(Ethereum { chain_id: 1 });
+    let evm_account = AccountKey20 {
+        network: None,
+        key: [7u8; 20],
+    };
+    let bob_account = Junction::AccountId32 {
+        network: None,
+        id: BOB.into(),
+    };
+
+    ParaA::execute_with(|| {
+        register_name(&state_manager, ALICE, "alice").unwrap();
+
+        // Only the owner can set address records
+        let address = Address::V1(1, Some(2), BOB);
+        let rs = set_address_for(&state_manager, BOB, "alice", para_b, Some(&address));
+        assert!(rs.is_err());
+
+        let rs = set_address_for(&state_manager, ALICE, "alice", para_b, Some(&address));
+        assert_eq!(rs, Ok(()));
+
+        let interior = vec![GlobalConsensus(Ethereum { chain_id: 1 }), evm_account];
+        let address = Address::V2(2, interior);
+        let rs = set_address_for(&state_manager, ALICE, "alice", ethereum, Some(&address));
+        assert_eq!(rs, Ok(()));
+
+        // Records do not affect the default address
+        let rs = get_address(&state_manager, "alice");
+        assert_eq!(rs, None);
+
+        let para_loc = VersionedMultiLocation::V3((Parent, Parachain(2), bob_account).into());
+        let rs = get_address_for(&state_manager, "alice", para_b);
+        assert_eq!(rs, Some(para_loc.clone()));
+        let rs = get_address_for(&state_manager, "alice", TargetChain::Relay);
+        assert_eq!(rs, None);
+
+        let interior = X2(GlobalConsensus(Ethereum { chain_id: 1 }), evm_account);
+        let evm_loc = VersionedMultiLocation::V3(MultiLocation::new(2, interior));
+        let rs = get_all_addresses(&state_manager, "alice");
+        assert_eq!(rs, vec![(para_b, para_loc), (ethereum, evm_loc)]);
+
+        // Names are normalized, as in the other getters
+        let rs = get_all_addresses(&state_manager, "Alice");
+        assert_eq!(rs.len(), 2);
+
+        // Removing a record
+        let rs = set_address_for(&state_manager, ALICE, "alice", ethereum, None);
+        assert_eq!(rs, Ok(()));
+        let rs = get_all_addresses(&state_manager, "alice");
+        assert_eq!(rs.len(), 1);
+
+        // Records are cleared on transfer
+        transfer_name(&state_manager, ALICE, "alice", BOB).unwrap();
+        let rs = get_all_addresses(&state_manager, "alice");
+        assert_eq!(rs, vec![]);
+    });
+}
+
+#[test]
+fn text_records_work() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+
+    ParaA::execute_with(|| {
+        register_name(&state_manager, ALICE, "alice").unwrap();
+
+        // Only the owner can set records
+        let records = [("url", Some("https://alice.xyz"))];
+        let rs = set_records(&state_manager, BOB, "alice", &records);
+        assert!(rs.is_err());
+
+        let records = [
+            ("url", Some("https://alice.xyz")),
+            ("email", Some("a@alice.xyz")),
+        ];
+        let rs = set_records(&state_manager, ALICE, "alice", &records);
+        assert_eq!(rs, Ok(()));
+
+        // Updating & removing records leaves the others untouched
+        let records = [("url", Some("https://alice.dev")), ("email", None)];
+        let rs = set_records(&state_manager, ALICE, "alice", &records);
+        assert_eq!(rs, Ok(()));
+
+        let records = vec![("url".to_string(), "https://alice.dev".to_string())];
+        let rs = get_records(&state_manager, "alice");
+        assert_eq!(rs, records);
+        let rs = get_record(&state_manager, "alice", "email");
+        assert_eq!(rs, None);
+
+        // Records are bounded in length & count
+        let long_key = "k".repeat(33);
+        let records = [(long_key.as_str(), Some("value"))];
+        let rs = set_records(&state_manager, ALICE, "alice", &records);
+        assert!(rs.is_err());
+
+        let keys: Vec<_> = (0..16).map(|i| i.to_string()).collect();
+        let records: Vec<_> = keys.iter().map(|k| (k.as_str(), Some("value"))).collect();
+        let rs = set_records(&state_manager, ALICE, "alice", &records);
+        assert!(rs.is_err());
+
+        // Records are cleared on transfer
+        transfer_name(&state_manager, ALICE, "alice", BOB).unwrap();
+        let rs = get_records(&state_manager, "alice");
+        assert_eq!(rs, vec![]);
+    });
+}
+
 #[test]
 fn transfer_name_works() {
     MockNet::reset();
@@ -118,3 +617,385 @@ fn transfer_name_works() {
         assert_eq!(rs, Some(BOB));
     });
 }
+
+#[test]
+fn events_are_emitted() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+
+    ParaA::execute_with(|| {
+        register_name(&state_manager, ALICE, "alice").unwrap();
+        transfer_name(&state_manager, ALICE, "alice", BOB).unwrap();
+
+        let name_hash = sp_core::blake2_256(b"alice");
+        let events = contract_events::<DomainServiceEvent>(&state_manager);
+        assert_eq!(events.len(), 2);
+
+        let registered = DomainServiceEvent::NameRegistered {
+            name_hash,
+            name: "alice".to_string(),
+            owner: ALICE,
+            expiry: get_expiry(&state_manager, "alice").unwrap(),
+            price: 100,
+            via_xcm: false,
+            origin: None,
+        };
+        assert_eq!(events[0], registered);
+
+        let transferred = DomainServiceEvent::NameTransferred {
+            name_hash,
+            name: "alice".to_string(),
+            from: ALICE,
+            to: BOB,
+            via_xcm: false,
+            origin: None,
+        };
+        assert_eq!(events[1], transferred);
+    });
+}
+
+#[test]
+fn expired_name_can_be_reclaimed() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+
+    ParaA::execute_with(|| {
+        setup::fund_address(&BOB);
+
+        // Registration lasts 10 blocks followed by 5 blocks of grace period
+        set_periods(&state_manager, 10, 5);
+        register_name(&state_manager, ALICE, "alice").unwrap();
+        assert_eq!(get_expiry(&state_manager, "alice"), Some(11));
+
+        // Expired names resolve to nothing
+        parachain::System::set_block_number(11);
+        assert_eq!(get_owner(&state_manager, "alice"), None);
+
+        // The owner can no longer manage the name...
+        let rs = transfer_name(&state_manager, ALICE, "alice", BOB);
+        assert!(rs.is_err());
+
+        // ...and nobody else can claim it during the grace period
+        let rs = register_name(&state_manager, BOB, "alice");
+        assert!(rs.is_err());
+
+        // Once the grace period is over the name is free again
+        parachain::System::set_block_number(16);
+        let rs = register_name(&state_manager, BOB, "alice");
+        assert_eq!(rs, Ok(()));
+        assert_eq!(get_owner(&state_manager, "alice"), Some(BOB));
+    });
+}
+
+#[test]
+fn subdomains_work() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+
+    ParaA::execute_with(|| {
+        setup::fund_address(&BOB);
+        register_name(&state_manager, ALICE, "alice").unwrap();
+
+        // Subdomains can only be created by the owner of the parent
+        let rs = set_subdomain_owner(&state_manager, BOB, "alice", "pay", BOB);
+        assert!(rs.is_err());
+        let rs = register_name(&state_manager, BOB, "pay.alice");
+        assert!(rs.is_err());
+
+        let rs = set_subdomain_owner(&state_manager, ALICE, "alice", "pay", BOB);
+        assert_eq!(rs, Ok(()));
+        assert_eq!(get_owner(&state_manager, "pay.alice"), Some(BOB));
+        let rs = get_subdomains(&state_manager, "alice");
+        assert_eq!(rs, vec!["pay".to_string()]);
+
+        // The subdomain owner manages its own records
+        let address = Address::V1(0, None, BOB);
+        let rs = set_address(&state_manager, BOB, "pay.alice", &address);
+        assert_eq!(rs, Ok(()));
+        let rs = set_address(&state_manager, ALICE, "pay.alice", &address);
+        assert!(rs.is_err());
+
+        // The parent can revoke the subdomain...
+        let rs = revoke_subdomain(&state_manager, ALICE, "alice", "pay");
+        assert_eq!(rs, Ok(()));
+        assert_eq!(get_owner(&state_manager, "pay.alice"), None);
+        assert_eq!(get_address(&state_manager, "pay.alice"), None);
+
+        // ...unless it is locked
+        set_subdomain_owner(&state_manager, ALICE, "alice", "vault", BOB).unwrap();
+        let rs = lock_subdomain(&state_manager, ALICE, "alice", "vault");
+        assert_eq!(rs, Ok(()));
+
+        let rs = revoke_subdomain(&state_manager, ALICE, "alice", "vault");
+        assert!(rs.is_err());
+        let rs = set_subdomain_owner(&state_manager, ALICE, "alice", "vault", ALICE);
+        assert!(rs.is_err());
+
+        // The subdomain owner can still transfer it
+        let rs = transfer_name(&state_manager, BOB, "vault.alice", ALICE);
+        assert_eq!(rs, Ok(()));
+        assert_eq!(get_owner(&state_manager, "vault.alice"), Some(ALICE));
+
+        // Subdomains expire along with their parent
+        let expiry = get_expiry(&state_manager, "alice").unwrap();
+        assert_eq!(get_expiry(&state_manager, "vault.alice"), Some(expiry));
+        parachain::System::set_block_number(expiry);
+        assert_eq!(get_owner(&state_manager, "vault.alice"), None);
+    });
+}
+
+#[test]
+fn primary_name_works() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+
+    ParaA::execute_with(|| {
+        setup::fund_address(&BOB);
+        register_name(&state_manager, ALICE, "alice").unwrap();
+
+        // The primary name must be owned by the caller...
+        let rs = set_primary_name(&state_manager, BOB, Some("alice"));
+        assert!(rs.is_err());
+
+        // ...and forward-resolve to it
+        let rs = set_primary_name(&state_manager, ALICE, Some("alice"));
+        assert!(rs.is_err());
+        set_address(&state_manager, ALICE, "alice", &Address::V1(0, None, BOB)).unwrap();
+        let rs = set_primary_name(&state_manager, ALICE, Some("alice"));
+        assert!(rs.is_err());
+
+        set_address(&state_manager, ALICE, "alice", &Address::V1(0, None, ALICE)).unwrap();
+        let rs = set_primary_name(&state_manager, ALICE, Some("alice"));
+        assert_eq!(rs, Ok(()));
+        let rs = get_primary_name(&state_manager, ALICE);
+        assert_eq!(rs, Some("alice".to_string()));
+
+        // It no longer resolves once the address changes...
+        set_address(&state_manager, ALICE, "alice", &Address::V1(0, None, BOB)).unwrap();
+        let rs = get_primary_name(&state_manager, ALICE);
+        assert_eq!(rs, None);
+
+        // ...or the name is transferred...
+        set_address(&state_manager, ALICE, "alice", &Address::V1(0, None, ALICE)).unwrap();
+        transfer_name(&state_manager, ALICE, "alice", BOB).unwrap();
+        let rs = get_primary_name(&state_manager, ALICE);
+        assert_eq!(rs, None);
+
+        // ...and can be unset
+        set_address(&state_manager, BOB, "alice", &Address::V1(0, None, BOB)).unwrap();
+        set_primary_name(&state_manager, BOB, Some("alice")).unwrap();
+        let rs = get_primary_name(&state_manager, BOB);
+        assert_eq!(rs, Some("alice".to_string()));
+        let rs = set_primary_name(&state_manager, BOB, None);
+        assert_eq!(rs, Ok(()));
+        let rs = get_primary_name(&state_manager, BOB);
+        assert_eq!(rs, None);
+    });
+}
+
+#[test]
+fn psp34_works() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+
+    ParaA::execute_with(|| {
+        setup::fund_address(&BOB);
+        register_name(&state_manager, ALICE, "alice").unwrap();
+        register_name(&state_manager, ALICE, "alice2").unwrap();
+        set_address(&state_manager, ALICE, "alice", &Address::V1(0, None, ALICE)).unwrap();
+        let records = [("url", Some("alice.me"))];
+        set_records(&state_manager, ALICE, "alice", &records).unwrap();
+
+        assert_eq!(psp34_owner_of(&state_manager, "alice"), Some(ALICE));
+        assert_eq!(psp34_balance_of(&state_manager, ALICE), 2);
+        // Ids must hold the normalized name
+        assert_eq!(psp34_owner_of(&state_manager, "ALICE"), None);
+        let rs = psp34_token_by_index(&state_manager, 1);
+        assert_eq!(rs, Ok(token_id("alice2")));
+
+        let rs = psp34_get_attribute(&state_manager, "alice", "name");
+        assert_eq!(rs, Some(b"alice".to_vec()));
+        let rs = psp34_get_attribute(&state_manager, "alice", "url");
+        assert_eq!(rs, Some(b"alice.me".to_vec()));
+
+        // BOB needs an approval to transfer the name
+        let rs = psp34_transfer(&state_manager, BOB, BOB, "alice");
+        assert_eq!(rs, Err(PSP34Error::NotApproved));
+
+        let rs = psp34_approve(&state_manager, ALICE, BOB, Some("alice"), true);
+        assert_eq!(rs, Ok(()));
+
+        // Transferring to the owner is rejected, rather than clearing the name's data
+        let rs = psp34_transfer(&state_manager, BOB, ALICE, "alice");
+        assert_eq!(rs, Err(PSP34Error::Custom("SameOwner".into())));
+        assert!(transfer_name(&state_manager, ALICE, "alice", ALICE).is_err());
+        let rs = psp34_get_attribute(&state_manager, "alice", "url");
+        assert_eq!(rs, Some(b"alice.me".to_vec()));
+        assert!(get_address(&state_manager, "alice").is_some());
+
+        let rs = psp34_transfer(&state_manager, BOB, BOB, "alice");
+        assert_eq!(rs, Ok(()));
+
+        // Same as `transfer_name`, the resolving address is cleared
+        assert_eq!(psp34_owner_of(&state_manager, "alice"), Some(BOB));
+        assert_eq!(get_address(&state_manager, "alice"), None);
+        assert_eq!(psp34_balance_of(&state_manager, ALICE), 1);
+        let rs = psp34_owners_token_by_index(&state_manager, ALICE, 0);
+        assert_eq!(rs, Ok(token_id("alice2")));
+        let rs = psp34_owners_token_by_index(&state_manager, ALICE, 1);
+        assert_eq!(rs, Err(PSP34Error::TokenNotExists));
+        let rs = psp34_owners_token_by_index(&state_manager, BOB, 0);
+        assert_eq!(rs, Ok(token_id("alice")));
+
+        // The approval does not survive the transfer
+        let rs = psp34_transfer(&state_manager, BOB, ALICE, "alice2");
+        assert_eq!(rs, Err(PSP34Error::NotApproved));
+
+        let events = contract_events::<DomainServiceEvent>(&state_manager);
+        let minted = DomainServiceEvent::Transfer {
+            from: None,
+            to: Some(ALICE),
+            id: token_id("alice"),
+        };
+        let approved = DomainServiceEvent::Approval {
+            owner: ALICE,
+            operator: BOB,
+            id: Some(token_id("alice")),
+            approved: true,
+        };
+        let transferred = DomainServiceEvent::Transfer {
+            from: Some(ALICE),
+            to: Some(BOB),
+            id: token_id("alice"),
+        };
+        assert!(events.contains(&minted));
+        assert!(events.contains(&approved));
+        assert!(events.contains(&transferred));
+    });
+}
+
+#[test]
+fn managers_and_operators_work() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+    let charlie = AccountId32::new([2u8; 32]);
+
+    ParaA::execute_with(|| {
+        setup::fund_address(&BOB);
+        setup::fund_address(&charlie);
+        register_name(&state_manager, ALICE, "alice").unwrap();
+        register_name(&state_manager, ALICE, "alice2").unwrap();
+
+        let address = Address::V1(0, None, BOB);
+        let rs = set_address(&state_manager, BOB, "alice", &address);
+        assert!(rs.is_err());
+
+        // A manager can update the address of the name...
+        set_manager(&state_manager, ALICE, "alice", Some(BOB)).unwrap();
+        assert_eq!(get_manager(&state_manager, "alice"), Some(BOB));
+        let rs = set_address(&state_manager, BOB, "alice", &address);
+        assert_eq!(rs, Ok(()));
+
+        // ...but not appoint another manager nor manage other names
+        let rs = set_manager(&state_manager, BOB, "alice", Some(charlie.clone()));
+        assert!(rs.is_err());
+        let rs = set_address(&state_manager, BOB, "alice2", &address);
+        assert!(rs.is_err());
+
+        // An operator can manage all the names of the owner
+        set_operator(&state_manager, ALICE, charlie.clone(), true).unwrap();
+        let rs = set_address(&state_manager, charlie.clone(), "alice2", &address);
+        assert_eq!(rs, Ok(()));
+        let rs = transfer_name(&state_manager, charlie.clone(), "alice2", BOB);
+        assert_eq!(rs, Ok(()));
+        assert_eq!(get_owner(&state_manager, "alice2"), Some(BOB));
+
+        // The manager is reset once the name changes hands
+        transfer_name(&state_manager, BOB, "alice", charlie.clone()).unwrap();
+        assert_eq!(get_owner(&state_manager, "alice"), Some(charlie));
+        assert_eq!(get_manager(&state_manager, "alice"), None);
+    });
+}
+
+#[test]
+fn renewal_works() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+
+    ParaA::execute_with(|| {
+        setup::fund_address(&BOB);
+        set_periods(&state_manager, 10, 5);
+        register_name(&state_manager, ALICE, "alice").unwrap();
+
+        // Only the owner can renew
+        let rs = renew_name(&state_manager, BOB, "alice");
+        assert!(rs.is_err());
+
+        // Renewal within the grace period extends from the previous expiry
+        parachain::System::set_block_number(13);
+        let rs = renew_name(&state_manager, ALICE, "alice");
+        assert_eq!(rs, Ok(()));
+        assert_eq!(get_expiry(&state_manager, "alice"), Some(21));
+        assert_eq!(get_owner(&state_manager, "alice"), Some(ALICE));
+    });
+}
+
+#[test]
+fn pricing_works() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+
+    ParaA::execute_with(|| {
+        // 1-char: 500, 2-char: 200, longer: 100 per year
+        // First year is charged in full, subsequent years at 50%
+        set_price_config(&state_manager, vec![500, 200, 100], vec![100, 50]);
+
+        assert_eq!(get_price(&state_manager, "a", 1), 500);
+        assert_eq!(get_price(&state_manager, "ab", 1), 200);
+        assert_eq!(get_price(&state_manager, "alice", 1), 100);
+        assert_eq!(get_price(&state_manager, "alice", 3), 200);
+
+        // Insufficient payment is rejected
+        let rs = register_name(&state_manager, ALICE, "ab");
+        assert!(rs.is_err());
+
+        let rs = register_name(&state_manager, ALICE, "abc");
+        assert_eq!(rs, Ok(()));
+    });
+}
+
+#[test]
+fn fee_split_and_withdrawal_work() {
+    MockNet::reset();
+    let (state_manager, _, _) = setup::setup();
+    let referrer = AccountId32::new([2u8; 32]);
+
+    ParaA::execute_with(|| {
+        setup::fund_address(&BOB);
+        let initial = get_withdrawable_balance(&state_manager);
+
+        // Shares must add up to 100
+        assert!(set_fee_split(&state_manager, (50, 30, 30)).is_err());
+        assert_eq!(set_fee_split(&state_manager, (50, 30, 20)), Ok(()));
+
+        // The referrer is paid right away and the locked share can't be withdrawn
+        let rs = register_name_with_referrer(&state_manager, BOB, "bob", referrer.clone());
+        assert_eq!(rs, Ok(()));
+        assert_eq!(ParachainBalances::free_balance(&referrer), 30);
+        assert_eq!(get_withdrawable_balance(&state_manager), initial + 50);
+
+        // Without a referrer, the treasury keeps its share
+        register_name(&state_manager, ALICE, "alice").unwrap();
+        assert_eq!(get_withdrawable_balance(&state_manager), initial + 130);
+
+        // Only the admin can withdraw, up to the withdrawable balance
+        assert!(withdraw(&state_manager, BOB, 130, BOB).is_err());
+        let rs = withdraw(&state_manager, ALICE, initial + 131, BOB);
+        assert!(rs.is_err());
+
+        let balance = ParachainBalances::free_balance(&BOB);
+        assert_eq!(withdraw(&state_manager, ALICE, 130, BOB), Ok(()));
+        assert_eq!(ParachainBalances::free_balance(&BOB), balance + 130);
+        assert_eq!(get_withdrawable_balance(&state_manager), initial);
+    });
+}
@@ -16,12 +16,23 @@ fn deploy_xcm_handler(admin: &AccountId32, state_manager: &AccountId32) -> Accou
 
     let sel_constructor = get_selector("new");
     let custom_wt: Option<(u64, u64)> = None;
-    let payload = (sel_constructor, admin, state_manager, custom_wt).encode(); // (selector, admin, state_manager, custom_wt)
+    let universal_location = Some(1u32); // ParaA
+
+    // (selector, admin, state_manager, universal_location, custom_wt)
+    let payload = (
+        sel_constructor,
+        admin,
+        state_manager,
+        universal_location,
+        custom_wt,
+    )
+        .encode();
 
     deploy_contract(blob, payload, ALICE)
 }
 
 pub fn deploy_xc_contract(
+    admin: &AccountId32,
     xcm_handler: &AccountId32,
     xcm_handler_soac: &AccountId32,
 ) -> AccountId32 {
@@ -30,7 +41,23 @@ pub fn deploy_xc_contract(
 
     let sel_constructor = get_selector("new");
     let custom_wt: Option<(u64, u64)> = None;
-    let payload = (sel_constructor, xcm_handler, xcm_handler_soac, custom_wt).encode(); // (selector, xcm_handler, xcm_handler_soac, custom_wt)
+    let (universal_location, hub_location) = (Some(2u32), Some(1u32)); // (ParaB, ParaA)
+    let fee_estimator: FeeEstimator = ((1, 1), 1, 1); // Weigher & Trader of the mock parachains
+
+    // (selector, admin, xcm_handler, xcm_handler_soac, universal_location, hub_location, custom_wt,
+    //  fee_estimator, hub_fee_estimator)
+    let payload = (
+        sel_constructor,
+        admin,
+        xcm_handler,
+        xcm_handler_soac,
+        universal_location,
+        hub_location,
+        custom_wt,
+        fee_estimator,
+        fee_estimator,
+    )
+        .encode();
 
     deploy_contract(blob, payload, ALICE)
 }
@@ -81,7 +108,8 @@ pub fn setup() -> (AccountId32, AccountId32, AccountId32) {
     ParaA::execute_with(|| set_handler(&state_manager, &xcm_handler));
 
     // 3A. Deploy `xc_domain_service: xc-contract`
-    let xc_contract = ParaB::execute_with(|| deploy_xc_contract(&xcm_handler, &xcm_handler_soac));
+    let xc_contract =
+        ParaB::execute_with(|| deploy_xc_contract(&ALICE, &xcm_handler, &xcm_handler_soac));
     let xc_contract_soac = sibling_account_account_id(2, xc_contract.clone());
     println!("xc_contract: {:?}", xc_contract);
 
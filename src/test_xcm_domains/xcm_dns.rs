@@ -2,11 +2,199 @@ use super::*;
 
 pub type TicketId = u128;
 
-pub fn register_name(xc_contract: &AccountId32, caller: AccountId32, name: &str) -> Result<(), u8> {
+/// Mirrors `utils::HandlerError` with domain-service errors as raw codes
+#[derive(Debug, PartialEq, Decode)]
+pub enum HandlerError {
+    NotAdmin,
+    UnknownCaller,
+    CallRuntimeFailed,
+    InkEnvError,
+    DomainService(u8),
+    UnsupportedXcmVersion,
+    UnsupportedMultiLocationFormat,
+    ReanchoringFailed,
+    InvalidBatchSize,
+}
+
+/// Mirrors `xc_domain_service::TicketStatus`
+#[derive(Debug, PartialEq, Decode)]
+pub enum TicketStatus {
+    AwaitingResponse,
+    Ready,
+    Succeeded,
+    Failed(HandlerError),
+    Expired,
+}
+
+/// Mirrors `xc_domain_service::CallbackStatus`
+#[derive(Debug, PartialEq, Decode)]
+pub enum CallbackStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+/// Mirrors `xc_domain_service::Callback`
+#[derive(Debug, PartialEq, Decode)]
+pub struct Callback {
+    pub contract: AccountId32,
+    pub selector: [u8; 4],
+    pub status: CallbackStatus,
+}
+
+/// Mirrors `utils::XcmFeeMode` with the asset's `ChainLocation` as `para_id`
+#[derive(Encode)]
+pub enum XcmFeeMode {
+    Unpaid,
+    Paid { asset: Option<u32>, amount: Balance },
+}
+
+/// Mirrors `utils::Operation`
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub enum Operation {
+    GetOwner,
+    GetAddress,
+    GetPrice,
+    GetOwners,
+    GetAddresses,
+    RegisterName,
+    RenewName,
+    TransferName,
+    SetAddress,
+    SetAddressFor,
+    GetRecords,
+    SetRecords,
+    SetSubdomainOwner,
+    RevokeSubdomain,
+    LockSubdomain,
+    GetName,
+    SetPrimaryName,
+    SetManager,
+    SetOperator,
+}
+
+/// Mirrors the events of `xc_domain_service`
+#[derive(Debug, PartialEq, Decode)]
+pub enum XcDomainServiceEvent {
+    ResponseReceived {
+        ticket_id: TicketId,
+    },
+    PaymentRefunded {
+        ticket_id: TicketId,
+        to: AccountId32,
+        amount: Balance,
+    },
+    RequestSent {
+        ticket_id: TicketId,
+        op: Operation,
+        name_hash: Option<[u8; 32]>,
+    },
+    Withdrawn {
+        to: AccountId32,
+        amount: Balance,
+    },
+    FeesForwarded {
+        asset: Option<u32>,
+        amount: Balance,
+        beneficiary: AccountId32,
+    },
+}
+
+/// Mirrors the events of `xcm_handler`
+#[derive(Debug, PartialEq, Decode)]
+pub enum XcmHandlerEvent {
+    RequestHandled {
+        ticket_id: TicketId,
+        origin: MultilocationEncoded,
+        op: Operation,
+        result: Option<Result<Balance, HandlerError>>,
+    },
+    ResponseDispatched {
+        ticket_id: TicketId,
+        origin: MultilocationEncoded,
+    },
+}
+
+/// `DomainServiceError::NotOwner`
+pub const ERR_NOT_OWNER: u8 = 2;
+/// `DomainServiceError::RecordTooLong`
+pub const ERR_RECORD_TOO_LONG: u8 = 12;
+/// `DomainServiceError::SubdomainLocked`
+pub const ERR_SUBDOMAIN_LOCKED: u8 = 14;
+/// `DomainServiceError::NotResolvingToCaller`
+pub const ERR_NOT_RESOLVING_TO_CALLER: u8 = 19;
+/// `xc_domain_service::Error::CallRuntimeFailed`
+pub const ERR_CALL_RUNTIME_FAILED: u8 = 2;
+/// `xc_domain_service::Error::AwaitingResponse`
+pub const ERR_AWAITING_RESPONSE: u8 = 5;
+/// `xc_domain_service::Error::NotAdmin`
+pub const ERR_NOT_ADMIN: u8 = 10;
+/// `xc_domain_service::Error::InvalidBatchSize`
+pub const ERR_INVALID_BATCH_SIZE: u8 = 13;
+/// `xc_domain_service::Error::InvalidName`
+pub const ERR_INVALID_NAME: u8 = 14;
+/// `xc_domain_service::Error::InsufficientFunds`
+pub const ERR_INSUFFICIENT_FUNDS: u8 = 15;
+/// `xc_domain_service::Error::PaymentInEscrow`
+pub const ERR_PAYMENT_IN_ESCROW: u8 = 17;
+/// `xc_domain_service::Error::RequestFailed`
+pub const ERR_REQUEST_FAILED: u8 = 18;
+
+/// Account acting on the hub on behalf of `who` calling from ParaB
+pub fn hub_account(who: AccountId32) -> AccountId32 {
+    sibling_account_account_id(2, who)
+}
+
+pub fn set_alias_mode(xcm_handler: &AccountId32, para_id: u32, enabled: bool) {
+    let sel_set_alias_mode = get_selector("set_alias_mode");
+    let payload = (sel_set_alias_mode, Some(para_id), enabled).encode();
+
+    let encoded_resp = call_contract(&xcm_handler, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
+
+    assert_eq!(resp, Ok(()));
+}
+
+pub fn set_universal_location(xcm_handler: &AccountId32, universal_location: Option<u32>) {
+    let sel_set_universal_location = get_selector("set_universal_location");
+    let payload = (sel_set_universal_location, universal_location).encode();
+
+    let encoded_resp = call_contract(&xcm_handler, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
+
+    assert_eq!(resp, Ok(()));
+}
+
+pub fn get_address_for_origin(
+    xcm_handler: &AccountId32,
+    name: &str,
+    origin_path: &MultilocationEncoded,
+) -> Result<Option<VersionedMultiLocation>, HandlerError> {
+    let sel_get_address_for_origin = get_selector("get_address_for_origin");
+    let payload = (sel_get_address_for_origin, name, origin_path).encode();
+
+    let encoded_resp = call_contract(&xcm_handler, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn register_name(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+) -> Result<TicketId, u8> {
+    register_name_paying(xc_contract, caller, name, 100)
+}
+
+pub fn register_name_paying(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    value: Balance,
+) -> Result<TicketId, u8> {
     let sel_register_name = get_selector("register_name");
-    let payload = (sel_register_name, name).encode();
+    let payload = (sel_register_name, name, 1u8).encode(); // (selector, name, years)
 
-    let encoded_resp = call_contract(&xc_contract, caller, payload, 100);
+    let encoded_resp = call_contract(&xc_contract, caller, payload, value);
     Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
 }
 
@@ -15,7 +203,7 @@ pub fn transfer_name(
     caller: AccountId32,
     name: &str,
     receiver: AccountId32,
-) -> Result<(), u8> {
+) -> Result<TicketId, u8> {
     let sel_transfer_name = get_selector("transfer_name");
     let payload = (sel_transfer_name, name, receiver).encode();
 
@@ -27,8 +215,8 @@ pub fn set_address(
     xc_contract: &AccountId32,
     caller: AccountId32,
     name: &str,
-    address: &(u8, Option<u32>, AccountId32),
-) -> Result<(), u8> {
+    address: &Address,
+) -> Result<TicketId, u8> {
     let sel_set_address = get_selector("set_address");
     let payload = (sel_set_address, name, address).encode();
 
@@ -36,6 +224,20 @@ pub fn set_address(
     Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
 }
 
+pub fn set_address_for(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    chain: TargetChain,
+    address: Option<&Address>,
+) -> Result<TicketId, u8> {
+    let sel_set_address_for = get_selector("set_address_for");
+    let payload = (sel_set_address_for, name, chain, address).encode();
+
+    let encoded_resp = call_contract(&xc_contract, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
 pub fn get_owner(xc_contract: &AccountId32, name: &str) -> Result<TicketId, u8> {
     let sel_get_owner = get_selector("get_owner");
     let payload = (sel_get_owner, name).encode();
@@ -52,126 +254,1710 @@ pub fn get_address(xc_contract: &AccountId32, name: &str) -> Result<TicketId, u8
     Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
 }
 
-pub fn retrieve_owner(xc_contract: &AccountId32, tid: TicketId) -> Result<Option<AccountId32>, u8> {
-    let sel_retrieve_owner = get_selector("retrieve_owner");
-    let payload = (sel_retrieve_owner, tid).encode();
+pub fn get_owner_with_callback(
+    xc_contract: &AccountId32,
+    name: &str,
+    callback: &AccountId32,
+    selector: [u8; 4],
+) -> Result<TicketId, u8> {
+    let sel_get_owner_with_callback = get_selector("get_owner_with_callback");
+    let payload = (sel_get_owner_with_callback, name, callback, selector).encode();
 
     let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
     Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
 }
 
-pub fn retrieve_address(
+pub fn get_callback(xc_contract: &AccountId32, tid: TicketId) -> Option<Callback> {
+    let sel_get_callback = get_selector("get_callback");
+    let payload = (sel_get_callback, tid).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_callback_gas_limit(xc_contract: &AccountId32, ref_time: u64, proof_size: u64) {
+    let sel_set_callback_gas_limit = get_selector("set_callback_gas_limit");
+    let payload = (sel_set_callback_gas_limit, ref_time, proof_size).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
+
+    assert_eq!(resp, Ok(()));
+}
+
+pub fn get_owners(xc_contract: &AccountId32, names: &[&str]) -> Result<TicketId, u8> {
+    let sel_get_owners = get_selector("get_owners");
+    let payload = (sel_get_owners, names).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_addresses(xc_contract: &AccountId32, names: &[&str]) -> Result<TicketId, u8> {
+    let sel_get_addresses = get_selector("get_addresses");
+    let payload = (sel_get_addresses, names).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_price(xc_contract: &AccountId32, name: &str, years: u8) -> Result<TicketId, u8> {
+    let sel_get_price = get_selector("get_price");
+    let payload = (sel_get_price, name, years).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn retrieve_price(xc_contract: &AccountId32, tid: TicketId) -> Result<Balance, u8> {
+    let sel_retrieve_price = get_selector("retrieve_price");
+    let payload = (sel_retrieve_price, tid).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_escrow(xc_contract: &AccountId32, tid: TicketId) -> Option<(AccountId32, Balance)> {
+    let sel_get_escrow = get_selector("get_escrow");
+    let payload = (sel_get_escrow, tid).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_ticket_status(xc_contract: &AccountId32, tid: TicketId) -> Result<TicketStatus, u8> {
+    let sel_get_ticket_status = get_selector("get_ticket_status");
+    let payload = (sel_get_ticket_status, tid).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn retrieve_write_outcome(
     xc_contract: &AccountId32,
     tid: TicketId,
-) -> Result<Option<VersionedMultiLocation>, u8> {
-    let sel_retrieve_address = get_selector("retrieve_address");
-    let payload = (sel_retrieve_address, tid).encode();
+) -> Result<Result<Balance, HandlerError>, u8> {
+    let sel_retrieve_write_outcome = get_selector("retrieve_write_outcome");
+    let payload = (sel_retrieve_write_outcome, tid).encode();
 
     let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
     Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
 }
 
-#[test]
-fn registration_works() {
-    MockNet::reset();
-    let (state_manager, _, xc_contract) = setup::setup();
+pub fn clear_ticket(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    tid: TicketId,
+) -> Result<(), u8> {
+    let sel_clear_ticket = get_selector("clear_ticket");
+    let payload = (sel_clear_ticket, tid).encode();
 
-    ParaB::execute_with(|| {
-        // Register a name
-        let rs = register_name(&xc_contract, ALICE, "alice");
-        assert_eq!(rs, Ok(()));
+    let encoded_resp = call_contract(&xc_contract, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
 
-        // Request for owner details
-        let rs = get_owner(&xc_contract, "alice");
-        assert_eq!(rs, Ok(0)); // tid = 0
-    });
+pub fn resolve_escrow(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    tid: TicketId,
+    refund: Balance,
+) -> Result<(), u8> {
+    let sel_resolve_escrow = get_selector("resolve_escrow");
+    let payload = (sel_resolve_escrow, tid, refund).encode();
 
-    ParaB::execute_with(|| {
-        // Retrieve owner details from request:0
-        let rs = retrieve_owner(&xc_contract, 0);
-        assert_eq!(rs, Ok(Some(ALICE)));
-    });
+    let encoded_resp = call_contract(&xc_contract, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
 
-    // Verify the state is updated on ParaA::state_manager as well
-    ParaA::execute_with(|| {
-        let rs = native_dns::get_owner(&state_manager, "alice");
-        assert_eq!(rs, Some(ALICE));
-    });
+pub fn set_locations(xc_contract: &AccountId32, para_id: u32, hub_para_id: u32) {
+    let sel_set_locations = get_selector("set_locations");
+    let payload = (sel_set_locations, Some(para_id), Some(hub_para_id)).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
+
+    assert_eq!(resp, Ok(()));
 }
 
-#[test]
-fn set_address_works() {
-    MockNet::reset();
-    let (state_manager, _, xc_contract) = setup::setup();
+/// (contracts_pallet, contracts_call, xcm_pallet, xcm_send, xcm_reserve_transfer, xcm_execute)
+pub type ChainProfile = (u8, u8, u8, u8, u8, u8);
 
-    ParaB::execute_with(|| {
-        // Register a name
-        let rs = register_name(&xc_contract, ALICE, "alice");
-        assert_eq!(rs, Ok(()));
+pub const DEFAULT_PROFILE: ChainProfile = (40, 6, 31, 0, 8, 3);
 
-        // Set domain's resolving address
-        let address = (0, None, ALICE);
-        let rs = set_address(&xc_contract, ALICE, "alice", &address);
-        assert_eq!(rs, Ok(()));
+pub fn set_chain_profiles(
+    xc_contract: &AccountId32,
+    chain_profile: ChainProfile,
+    hub_profile: ChainProfile,
+) {
+    let sel_set_chain_profiles = get_selector("set_chain_profiles");
+    let payload = (sel_set_chain_profiles, chain_profile, hub_profile).encode();
 
-        // Request for domain's resolving address
-        let rs = get_address(&xc_contract, "alice");
-        assert_eq!(rs, Ok(0)); // tid = 0
-    });
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
 
-    ParaB::execute_with(|| {
-        let true_ml = Junction::AccountId32 {
-            network: None,
-            id: ALICE.into(),
-        };
-        let loc = VersionedMultiLocation::V3(true_ml.into());
+    assert_eq!(resp, Ok(()));
+}
 
-        // Retrieve resolving address from request:0
-        let rs = retrieve_address(&xc_contract, 0);
-        assert_eq!(rs, Ok(Some(loc)));
-    });
+pub fn set_fee_mode(xc_contract: &AccountId32, fee_mode: XcmFeeMode) {
+    let sel_set_fee_mode = get_selector("set_fee_mode");
+    let payload = (sel_set_fee_mode, fee_mode).encode();
 
-    // Verify the state is updated on ParaA::state_manager as well
-    // and the MultiLocation anchoring is working properly
-    ParaA::execute_with(|| {
-        let account = Junction::AccountId32 {
-            network: None,
-            id: ALICE.into(),
-        };
-        let loc = VersionedMultiLocation::V3((Parent, Parachain(2), account).into());
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
 
-        let rs = native_dns::get_address(&state_manager, "alice");
-        assert_eq!(rs, Some(loc));
-    });
+    assert_eq!(resp, Ok(()));
 }
 
-#[test]
-fn transfer_works() {
-    MockNet::reset();
-    let (state_manager, _, xc_contract) = setup::setup();
+pub fn set_fee_estimators(
+    xc_contract: &AccountId32,
+    fee_estimator: FeeEstimator,
+    hub_fee_estimator: FeeEstimator,
+) {
+    let sel_set_fee_estimators = get_selector("set_fee_estimators");
+    let payload = (sel_set_fee_estimators, fee_estimator, hub_fee_estimator).encode();
 
-    ParaB::execute_with(|| {
-        // Register a name
-        register_name(&xc_contract, ALICE, "alice").unwrap();
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
 
-        // Transfer the name
-        let rs = transfer_name(&xc_contract, ALICE, "alice", BOB);
-        assert_eq!(rs, Ok(()));
+    assert_eq!(resp, Ok(()));
+}
 
-        // Request for owner details
-        let rs = get_owner(&xc_contract, "alice");
-        assert_eq!(rs, Ok(0)); // tid = 0
-    });
+pub fn set_operation_gas_limit(
+    xc_contract: &AccountId32,
+    operation: Operation,
+    gas_limit: Option<(u64, u64)>,
+) {
+    let sel_set_operation_gas_limit = get_selector("set_operation_gas_limit");
+    let payload = (sel_set_operation_gas_limit, operation, gas_limit).encode();
 
-    ParaB::execute_with(|| {
-        // Retrieve owner details from request:0
-        let rs = retrieve_owner(&xc_contract, 0);
-        assert_eq!(rs, Ok(Some(BOB)));
-    });
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
 
-    // Verify the state is updated on ParaA::state_manager as well
-    ParaA::execute_with(|| {
-        let rs = native_dns::get_owner(&state_manager, "alice");
-        assert_eq!(rs, Some(BOB));
+    assert_eq!(resp, Ok(()));
+}
+
+pub fn quote_fee(xc_contract: &AccountId32, operation: Operation) -> Balance {
+    let sel_quote_fee = get_selector("quote_fee");
+    let payload = (sel_quote_fee, operation).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_ticket_timeout(xc_contract: &AccountId32, timeout: u32) {
+    let sel_set_ticket_timeout = get_selector("set_ticket_timeout");
+    let payload = (sel_set_ticket_timeout, timeout).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
+
+    assert_eq!(resp, Ok(()));
+}
+
+pub fn get_my_tickets(
+    xc_contract: &AccountId32,
+    account: &AccountId32,
+    from: u32,
+    limit: u32,
+) -> Vec<TicketId> {
+    let sel_get_my_tickets = get_selector("get_my_tickets");
+    let payload = (sel_get_my_tickets, account, from, limit).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_my_ticket_slots(xc_contract: &AccountId32, account: &AccountId32) -> u32 {
+    let sel_get_my_ticket_slots = get_selector("get_my_ticket_slots");
+    let payload = (sel_get_my_ticket_slots, account).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_private_responses(xc_contract: &AccountId32, caller: AccountId32, enabled: bool) {
+    let sel_set_private_responses = get_selector("set_private_responses");
+    let payload = (sel_set_private_responses, enabled).encode();
+
+    call_contract(&xc_contract, caller, payload, 0);
+}
+
+pub fn read_raw_response(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    tid: TicketId,
+) -> Option<Vec<u8>> {
+    let sel_read_raw_response = get_selector("read_raw_response");
+    let payload = (sel_read_raw_response, tid).encode();
+
+    let encoded_resp = call_contract(&xc_contract, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn retrieve_owner(xc_contract: &AccountId32, tid: TicketId) -> Result<Option<AccountId32>, u8> {
+    let sel_retrieve_owner = get_selector("retrieve_owner");
+    let payload = (sel_retrieve_owner, tid).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn retrieve_address(
+    xc_contract: &AccountId32,
+    tid: TicketId,
+) -> Result<Option<VersionedMultiLocation>, u8> {
+    let sel_retrieve_address = get_selector("retrieve_address");
+    let payload = (sel_retrieve_address, tid).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn retrieve_owners(
+    xc_contract: &AccountId32,
+    tid: TicketId,
+) -> Result<Vec<Option<AccountId32>>, u8> {
+    let sel_retrieve_owners = get_selector("retrieve_owners");
+    let payload = (sel_retrieve_owners, tid).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn retrieve_addresses(
+    xc_contract: &AccountId32,
+    tid: TicketId,
+) -> Result<Vec<Option<VersionedMultiLocation>>, u8> {
+    let sel_retrieve_addresses = get_selector("retrieve_addresses");
+    let payload = (sel_retrieve_addresses, tid).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_records(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    records: &[(&str, Option<&str>)],
+) -> Result<TicketId, u8> {
+    let sel_set_records = get_selector("set_records");
+    let payload = (sel_set_records, name, records).encode();
+
+    let encoded_resp = call_contract(&xc_contract, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_records(xc_contract: &AccountId32, name: &str) -> Result<TicketId, u8> {
+    let sel_get_records = get_selector("get_records");
+    let payload = (sel_get_records, name).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn retrieve_records(
+    xc_contract: &AccountId32,
+    tid: TicketId,
+) -> Result<Vec<(String, String)>, u8> {
+    let sel_retrieve_records = get_selector("retrieve_records");
+    let payload = (sel_retrieve_records, tid).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_subdomain_owner(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    label: &str,
+    owner: AccountId32,
+) -> Result<TicketId, u8> {
+    let sel_set_subdomain_owner = get_selector("set_subdomain_owner");
+    let payload = (sel_set_subdomain_owner, name, label, owner).encode();
+
+    let encoded_resp = call_contract(&xc_contract, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn revoke_subdomain(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    label: &str,
+) -> Result<TicketId, u8> {
+    let sel_revoke_subdomain = get_selector("revoke_subdomain");
+    let payload = (sel_revoke_subdomain, name, label).encode();
+
+    let encoded_resp = call_contract(&xc_contract, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn lock_subdomain(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    label: &str,
+) -> Result<TicketId, u8> {
+    let sel_lock_subdomain = get_selector("lock_subdomain");
+    let payload = (sel_lock_subdomain, name, label).encode();
+
+    let encoded_resp = call_contract(&xc_contract, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_primary_name(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    name: Option<&str>,
+) -> Result<TicketId, u8> {
+    let sel_set_primary_name = get_selector("set_primary_name");
+    let payload = (sel_set_primary_name, name).encode();
+
+    let encoded_resp = call_contract(&xc_contract, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_manager(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    name: &str,
+    manager: Option<AccountId32>,
+) -> Result<TicketId, u8> {
+    let sel_set_manager = get_selector("set_manager");
+    let payload = (sel_set_manager, name, manager).encode();
+
+    let encoded_resp = call_contract(&xc_contract, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_operator(
+    xc_contract: &AccountId32,
+    caller: AccountId32,
+    operator: AccountId32,
+    approved: bool,
+) -> Result<TicketId, u8> {
+    let sel_set_operator = get_selector("set_operator");
+    let payload = (sel_set_operator, operator, approved).encode();
+
+    let encoded_resp = call_contract(&xc_contract, caller, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn get_name(xc_contract: &AccountId32, account: AccountId32) -> Result<TicketId, u8> {
+    let sel_get_name = get_selector("get_name");
+    let payload = (sel_get_name, account).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn retrieve_name(xc_contract: &AccountId32, tid: TicketId) -> Result<Option<String>, u8> {
+    let sel_retrieve_name = get_selector("retrieve_name");
+    let payload = (sel_retrieve_name, tid).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn withdraw(xc_contract: &AccountId32, amount: Balance, to: AccountId32) -> Result<(), u8> {
+    let sel_withdraw = get_selector("withdraw");
+    let payload = (sel_withdraw, amount, to).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+pub fn set_native_asset(xc_contract: &AccountId32, native_asset: Option<u32>) {
+    let sel_set_native_asset = get_selector("set_native_asset");
+    let payload = (sel_set_native_asset, native_asset).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
+
+    assert_eq!(resp, Ok(()));
+}
+
+pub fn set_xcm_instruction_weight(xc_contract: &AccountId32, ref_time: u64, proof_size: u64) {
+    let sel_set_xcm_instruction_weight = get_selector("set_xcm_instruction_weight");
+    let payload = (sel_set_xcm_instruction_weight, ref_time, proof_size).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    let resp: Result<(), u8> = Decode::decode(&mut &encoded_resp[..]).expect("failed to decode");
+
+    assert_eq!(resp, Ok(()));
+}
+
+pub fn forward_fees(
+    xc_contract: &AccountId32,
+    amount: Balance,
+    beneficiary: AccountId32,
+) -> Result<(), u8> {
+    let sel_forward_fees = get_selector("forward_fees");
+    let payload = (sel_forward_fees, amount, beneficiary).encode();
+
+    let encoded_resp = call_contract(&xc_contract, ALICE, payload, 0);
+    Decode::decode(&mut &encoded_resp[..]).expect("failed to decode")
+}
+
+/// Registers `name` on the hub with an address too deep to be re-anchored for ParaB
+pub fn set_unanchorable_address(state_manager: &AccountId32, name: &str) {
+    let interior = (0..8).map(GeneralIndex).collect();
+    native_dns::register_name(state_manager, ALICE, name).unwrap();
+    let address = Address::V2(0, interior);
+    native_dns::set_address(state_manager, ALICE, name, &address).unwrap();
+}
+
+#[test]
+fn registration_works() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        // Register a name
+        let rs = register_name(&xc_contract, ALICE, "alice");
+        assert_eq!(rs, Ok(0)); // tid = 0
+
+        // Request for owner details
+        let rs = get_owner(&xc_contract, "alice");
+        assert_eq!(rs, Ok(1)); // tid = 1
+    });
+
+    ParaB::execute_with(|| {
+        // Retrieve owner details from request:1
+        let rs = retrieve_owner(&xc_contract, 1);
+        assert_eq!(rs, Ok(Some(hub_account(ALICE))));
+    });
+
+    // Verify the state is updated on ParaA::state_manager as well
+    ParaA::execute_with(|| {
+        let rs = native_dns::get_owner(&state_manager, "alice");
+        assert_eq!(rs, Some(hub_account(ALICE)));
+
+        // The event records the xc-contract the request came from
+        let registered = DomainServiceEvent::NameRegistered {
+            name_hash: sp_core::blake2_256(b"alice"),
+            name: "alice".to_string(),
+            owner: hub_account(ALICE),
+            expiry: native_dns::get_expiry(&state_manager, "alice").unwrap(),
+            price: 100,
+            via_xcm: true,
+            origin: Some((1, Some(2), xc_contract.clone())),
+        };
+        let events = contract_events::<DomainServiceEvent>(&state_manager);
+        assert_eq!(events, vec![registered]);
+    });
+}
+
+#[test]
+fn names_are_validated_before_sending() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        // Invalid names are rejected without sending a message
+        let rs = register_name(&xc_contract, ALICE, "al ice");
+        assert_eq!(rs, Err(ERR_INVALID_NAME));
+        let rs = get_owners(&xc_contract, &["alice", ""]);
+        assert_eq!(rs, Err(ERR_INVALID_NAME));
+
+        // Valid names are sent normalized, no ticket was issued so far
+        let rs = register_name(&xc_contract, ALICE, "ＡＬＩＣＥ");
+        assert_eq!(rs, Ok(0)); // tid = 0
+    });
+
+    ParaA::execute_with(|| {
+        let rs = native_dns::get_owner(&state_manager, "alice");
+        assert_eq!(rs, Some(hub_account(ALICE)));
+    });
+}
+
+#[test]
+fn set_address_works() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        // Register a name
+        let rs = register_name(&xc_contract, ALICE, "alice");
+        assert_eq!(rs, Ok(0)); // tid = 0
+
+        // Set domain's resolving address
+        let address = Address::V1(0, None, ALICE);
+        let rs = set_address(&xc_contract, ALICE, "alice", &address);
+        assert_eq!(rs, Ok(1)); // tid = 1
+
+        // Request for domain's resolving address
+        let rs = get_address(&xc_contract, "alice");
+        assert_eq!(rs, Ok(2)); // tid = 2
+    });
+
+    ParaB::execute_with(|| {
+        let true_ml = Junction::AccountId32 {
+            network: None,
+            id: ALICE.into(),
+        };
+        let loc = VersionedMultiLocation::V3(true_ml.into());
+
+        // Retrieve resolving address from request:2
+        let rs = retrieve_address(&xc_contract, 2);
+        assert_eq!(rs, Ok(Some(loc)));
+    });
+
+    // Verify the state is updated on ParaA::state_manager as well
+    // and the MultiLocation anchoring is working properly
+    ParaA::execute_with(|| {
+        let account = Junction::AccountId32 {
+            network: None,
+            id: ALICE.into(),
+        };
+        let loc = VersionedMultiLocation::V3((Parent, Parachain(2), account).into());
+
+        let rs = native_dns::get_address(&state_manager, "alice");
+        assert_eq!(rs, Some(loc));
+    });
+}
+
+#[test]
+fn text_records_work() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        let records = [
+            ("url", Some("https://alice.xyz")),
+            ("twitter", Some("@alice")),
+        ];
+        let rs = set_records(&xc_contract, ALICE, "alice", &records);
+        assert_eq!(rs, Ok(1)); // tid = 1
+
+        let long_value = "x".repeat(257);
+        let records = [("avatar", Some(long_value.as_str()))];
+        let rs = set_records(&xc_contract, ALICE, "alice", &records);
+        assert_eq!(rs, Ok(2)); // tid = 2
+
+        let rs = get_records(&xc_contract, "alice");
+        assert_eq!(rs, Ok(3)); // tid = 3
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_ticket_status(&xc_contract, 1);
+        assert_eq!(rs, Ok(TicketStatus::Succeeded));
+
+        let err = HandlerError::DomainService(ERR_RECORD_TOO_LONG);
+        let rs = get_ticket_status(&xc_contract, 2);
+        assert_eq!(rs, Ok(TicketStatus::Failed(err)));
+
+        let records = vec![
+            ("url".to_string(), "https://alice.xyz".to_string()),
+            ("twitter".to_string(), "@alice".to_string()),
+        ];
+        let rs = retrieve_records(&xc_contract, 3);
+        assert_eq!(rs, Ok(records));
+    });
+
+    ParaA::execute_with(|| {
+        let rs = native_dns::get_record(&state_manager, "alice", "twitter");
+        assert_eq!(rs, Some("@alice".to_string()));
+    });
+}
+
+#[test]
+fn subdomains_can_be_managed_from_spokes() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        let rs = set_subdomain_owner(&xc_contract, ALICE, "alice", "pay", BOB);
+        assert_eq!(rs, Ok(1)); // tid = 1
+        let rs = set_subdomain_owner(&xc_contract, ALICE, "alice", "vault", BOB);
+        assert_eq!(rs, Ok(2)); // tid = 2
+        let rs = lock_subdomain(&xc_contract, ALICE, "alice", "vault");
+        assert_eq!(rs, Ok(3)); // tid = 3
+
+        let rs = revoke_subdomain(&xc_contract, ALICE, "alice", "pay");
+        assert_eq!(rs, Ok(4)); // tid = 4
+        let rs = revoke_subdomain(&xc_contract, ALICE, "alice", "vault");
+        assert_eq!(rs, Ok(5)); // tid = 5
+    });
+
+    ParaB::execute_with(|| {
+        for tid in 1..=4 {
+            let rs = get_ticket_status(&xc_contract, tid);
+            assert_eq!(rs, Ok(TicketStatus::Succeeded));
+        }
+
+        let err = HandlerError::DomainService(ERR_SUBDOMAIN_LOCKED);
+        let rs = get_ticket_status(&xc_contract, 5);
+        assert_eq!(rs, Ok(TicketStatus::Failed(err)));
+    });
+
+    ParaA::execute_with(|| {
+        let rs = native_dns::get_owner(&state_manager, "pay.alice");
+        assert_eq!(rs, None);
+        let rs = native_dns::get_owner(&state_manager, "vault.alice");
+        assert_eq!(rs, Some(BOB));
+    });
+}
+
+#[test]
+fn reverse_resolution_works() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        // The name does not resolve to ALICE yet
+        let rs = set_primary_name(&xc_contract, ALICE, Some("alice"));
+        assert_eq!(rs, Ok(1)); // tid = 1
+
+        let address = Address::V1(0, None, ALICE);
+        set_address(&xc_contract, ALICE, "alice", &address).unwrap(); // tid = 2
+        let rs = set_primary_name(&xc_contract, ALICE, Some("alice"));
+        assert_eq!(rs, Ok(3)); // tid = 3
+    });
+
+    ParaB::execute_with(|| {
+        let err = HandlerError::DomainService(ERR_NOT_RESOLVING_TO_CALLER);
+        let rs = get_ticket_status(&xc_contract, 1);
+        assert_eq!(rs, Ok(TicketStatus::Failed(err)));
+        let rs = get_ticket_status(&xc_contract, 3);
+        assert_eq!(rs, Ok(TicketStatus::Succeeded));
+
+        let rs = get_name(&xc_contract, ALICE);
+        assert_eq!(rs, Ok(4)); // tid = 4
+        let rs = get_name(&xc_contract, BOB);
+        assert_eq!(rs, Ok(5)); // tid = 5
+    });
+
+    ParaB::execute_with(|| {
+        let rs = retrieve_name(&xc_contract, 4);
+        assert_eq!(rs, Ok(Some("alice".to_string())));
+        let rs = retrieve_name(&xc_contract, 5);
+        assert_eq!(rs, Ok(None));
+    });
+
+    ParaA::execute_with(|| {
+        let rs = native_dns::get_primary_name(&state_manager, hub_account(ALICE));
+        assert_eq!(rs, Some("alice".to_string()));
+    });
+}
+
+#[test]
+fn managers_can_be_set_from_spokes() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        let rs = set_manager(&xc_contract, ALICE, "alice", Some(hub_account(BOB)));
+        assert_eq!(rs, Ok(1)); // tid = 1
+        let rs = set_operator(&xc_contract, ALICE, hub_account(BOB), true);
+        assert_eq!(rs, Ok(2)); // tid = 2
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_ticket_status(&xc_contract, 1);
+        assert_eq!(rs, Ok(TicketStatus::Succeeded));
+        let rs = get_ticket_status(&xc_contract, 2);
+        assert_eq!(rs, Ok(TicketStatus::Succeeded));
+
+        // The manager updates the address on behalf of the owner
+        let address = Address::V1(0, None, BOB);
+        set_address(&xc_contract, BOB, "alice", &address).unwrap(); // tid = 3
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_ticket_status(&xc_contract, 3);
+        assert_eq!(rs, Ok(TicketStatus::Succeeded));
+    });
+
+    ParaA::execute_with(|| {
+        let rs = native_dns::get_manager(&state_manager, "alice");
+        assert_eq!(rs, Some(hub_account(BOB)));
+        let rs = native_dns::get_owner(&state_manager, "alice");
+        assert_eq!(rs, Some(hub_account(ALICE)));
+    });
+}
+
+#[test]
+fn address_record_of_requesting_chain_is_picked() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    let account = |who: AccountId32| Junction::AccountId32 {
+        network: None,
+        id: who.into(),
+    };
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        let address = Address::V1(0, None, ALICE);
+        set_address(&xc_contract, ALICE, "alice", &address).unwrap(); // tid = 1
+
+        let address = Address::V1(0, None, BOB);
+        let chain = TargetChain::Parachain(2);
+        let rs = set_address_for(&xc_contract, ALICE, "alice", chain, Some(&address));
+        assert_eq!(rs, Ok(2)); // tid = 2
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_ticket_status(&xc_contract, 2);
+        assert_eq!(rs, Ok(TicketStatus::Succeeded));
+
+        let rs = get_address(&xc_contract, "alice");
+        assert_eq!(rs, Ok(3)); // tid = 3
+    });
+
+    ParaB::execute_with(|| {
+        let loc = VersionedMultiLocation::V3(account(BOB).into());
+        let rs = retrieve_address(&xc_contract, 3);
+        assert_eq!(rs, Ok(Some(loc)));
+    });
+
+    // Other chains still resolve to the default address
+    ParaA::execute_with(|| {
+        let loc = VersionedMultiLocation::V3((Parent, Parachain(2), account(ALICE)).into());
+        let rs = native_dns::get_address(&state_manager, "alice");
+        assert_eq!(rs, Some(loc));
+
+        let loc = VersionedMultiLocation::V3((Parent, Parachain(2), account(BOB)).into());
+        let rs = native_dns::get_address_for(&state_manager, "alice", TargetChain::Parachain(2));
+        assert_eq!(rs, Some(loc));
+    });
+}
+
+#[test]
+fn set_address_supports_versioned_locations() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    let evm_account = AccountKey20 {
+        network: None,
+        key: [7u8; 20],
+    };
+    let ethereum = GlobalConsensus(Ethereum { chain_id: 1 });
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+        register_name(&xc_contract, ALICE, "bob").unwrap(); // tid = 1
+        register_name(&xc_contract, ALICE, "asset").unwrap(); // tid = 2
+
+        // EVM account on this chain
+        let address = Address::V2(0, vec![evm_account]);
+        let rs = set_address(&xc_contract, ALICE, "alice", &address);
+        assert_eq!(rs, Ok(3)); // tid = 3
+
+        // EVM account beyond the relay-chain network
+        let address = Address::V2(2, vec![ethereum, evm_account]);
+        let rs = set_address(&xc_contract, ALICE, "bob", &address);
+        assert_eq!(rs, Ok(4)); // tid = 4
+
+        // Asset on this chain
+        let address = Address::V2(0, vec![PalletInstance(50), GeneralIndex(1984)]);
+        let rs = set_address(&xc_contract, ALICE, "asset", &address);
+        assert_eq!(rs, Ok(5)); // tid = 5
+    });
+
+    ParaB::execute_with(|| {
+        for tid in 3..=5 {
+            let rs = get_ticket_status(&xc_contract, tid);
+            assert_eq!(rs, Ok(TicketStatus::Succeeded));
+        }
+    });
+
+    ParaA::execute_with(|| {
+        let loc = VersionedMultiLocation::V3((Parent, Parachain(2), evm_account).into());
+        let rs = native_dns::get_address(&state_manager, "alice");
+        assert_eq!(rs, Some(loc));
+
+        let loc = VersionedMultiLocation::V3(MultiLocation::new(2, X2(ethereum, evm_account)));
+        let rs = native_dns::get_address(&state_manager, "bob");
+        assert_eq!(rs, Some(loc));
+
+        let interior = X3(Parachain(2), PalletInstance(50), GeneralIndex(1984));
+        let loc = VersionedMultiLocation::V3(MultiLocation::new(1, interior));
+        let rs = native_dns::get_address(&state_manager, "asset");
+        assert_eq!(rs, Some(loc));
+    });
+}
+
+#[test]
+fn account_network_is_kept() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    let account = Junction::AccountId32 {
+        network: Some(Polkadot),
+        id: BOB.into(),
+    };
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        let address = Address::V2(0, vec![account]);
+        let rs = set_address(&xc_contract, ALICE, "alice", &address);
+        assert_eq!(rs, Ok(1)); // tid = 1
+    });
+
+    ParaA::execute_with(|| {
+        let loc = VersionedMultiLocation::V3((Parent, Parachain(2), account).into());
+        let rs = native_dns::get_address(&state_manager, "alice");
+        assert_eq!(rs, Some(loc));
+    });
+}
+
+#[test]
+fn relay_hub_resolves_the_records_of_child_parachains() {
+    MockNet::reset();
+    let (state_manager, xcm_handler, xc_contract) = setup::setup();
+    // A child parachain reaches a relay-chain hub without any parent
+    let origin_path = (0, Some(2), xc_contract);
+
+    ParaA::execute_with(|| {
+        // The hub acts as the relay chain, addresses are set relative to it
+        set_universal_location(&xcm_handler, None);
+
+        native_dns::register_name(&state_manager, ALICE, "alice").unwrap();
+        let address = Address::V1(0, None, ALICE);
+        let rs = native_dns::set_address_for(
+            &state_manager,
+            ALICE,
+            "alice",
+            TargetChain::Relay,
+            Some(&address),
+        );
+        assert_eq!(rs, Ok(()));
+        let address = Address::V1(0, Some(2), BOB);
+        let rs = native_dns::set_address_for(
+            &state_manager,
+            ALICE,
+            "alice",
+            TargetChain::Parachain(2),
+            Some(&address),
+        );
+        assert_eq!(rs, Ok(()));
+
+        // The record of the parachain is picked rather than the hub's own
+        let account = Junction::AccountId32 {
+            network: None,
+            id: BOB.into(),
+        };
+        let loc = VersionedMultiLocation::V3(account.into());
+        let rs = get_address_for_origin(&xcm_handler, "alice", &origin_path);
+        assert_eq!(rs, Ok(Some(loc)));
+    });
+}
+
+#[test]
+fn requests_can_be_traced_through_events() {
+    MockNet::reset();
+    let (_, xcm_handler, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+        transfer_name(&xc_contract, BOB, "alice", BOB).unwrap(); // tid = 1
+        get_owner(&xc_contract, "alice").unwrap(); // tid = 2
+
+        let sent = XcDomainServiceEvent::RequestSent {
+            ticket_id: 0,
+            op: Operation::RegisterName,
+            name_hash: Some(sp_core::blake2_256(b"alice")),
+        };
+        let events = contract_events::<XcDomainServiceEvent>(&xc_contract);
+        assert!(events.contains(&sent));
+    });
+
+    ParaA::execute_with(|| {
+        let origin = (1, Some(2), xc_contract.clone());
+        let handled = |ticket_id, op, result| XcmHandlerEvent::RequestHandled {
+            ticket_id,
+            origin: origin.clone(),
+            op,
+            result,
+        };
+        let dispatched = |ticket_id| XcmHandlerEvent::ResponseDispatched {
+            ticket_id,
+            origin: origin.clone(),
+        };
+
+        // The failed transfer is reported along with the hub's error, and reads carry no outcome
+        let not_owner = HandlerError::DomainService(ERR_NOT_OWNER);
+        let expected = vec![
+            handled(0, Operation::RegisterName, Some(Ok(100))),
+            dispatched(0),
+            handled(1, Operation::TransferName, Some(Err(not_owner))),
+            dispatched(1),
+            handled(2, Operation::GetOwner, None),
+            dispatched(2),
+        ];
+        let events = contract_events::<XcmHandlerEvent>(&xcm_handler);
+        assert_eq!(events, expected);
+    });
+
+    ParaB::execute_with(|| {
+        let received = XcDomainServiceEvent::ResponseReceived { ticket_id: 1 };
+        let events = contract_events::<XcDomainServiceEvent>(&xc_contract);
+        assert!(events.contains(&received));
+    });
+}
+
+#[test]
+fn transfer_works() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        // Register a name
+        register_name(&xc_contract, ALICE, "alice").unwrap();
+
+        // Transfer the name
+        let rs = transfer_name(&xc_contract, ALICE, "alice", BOB);
+        assert_eq!(rs, Ok(1)); // tid = 1
+
+        // Request for owner details
+        let rs = get_owner(&xc_contract, "alice");
+        assert_eq!(rs, Ok(2)); // tid = 2
+    });
+
+    ParaB::execute_with(|| {
+        // Retrieve owner details from request:2
+        let rs = retrieve_owner(&xc_contract, 2);
+        assert_eq!(rs, Ok(Some(BOB)));
+    });
+
+    // Verify the state is updated on ParaA::state_manager as well
+    ParaA::execute_with(|| {
+        let rs = native_dns::get_owner(&state_manager, "alice");
+        assert_eq!(rs, Some(BOB));
+    });
+}
+
+#[test]
+fn price_quote_works() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaA::execute_with(|| {
+        native_dns::set_price_config(&state_manager, vec![500, 200, 100], vec![100]);
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_price(&xc_contract, "ab", 2);
+        assert_eq!(rs, Ok(0)); // tid = 0
+    });
+
+    ParaB::execute_with(|| {
+        let rs = retrieve_price(&xc_contract, 0);
+        assert_eq!(rs, Ok(400));
+    });
+}
+
+#[test]
+fn overpayment_is_refunded() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+    let mut initial_balance = 0;
+
+    ParaB::execute_with(|| {
+        initial_balance = ParachainBalances::free_balance(&xc_contract);
+
+        // Pay 150 for a name priced at 100
+        let rs = register_name_paying(&xc_contract, ALICE, "alice", 150);
+        assert_eq!(rs, Ok(0)); // tid = 0
+
+        // Payment is held until the hub responds
+        let rs = get_escrow(&xc_contract, 0);
+        assert_eq!(rs, Some((ALICE, 150)));
+    });
+
+    ParaB::execute_with(|| {
+        // Only the price is retained
+        assert_eq!(get_escrow(&xc_contract, 0), None);
+        let balance = ParachainBalances::free_balance(&xc_contract);
+        assert_eq!(balance, initial_balance + 100);
+    });
+}
+
+#[test]
+fn failed_registration_is_refunded() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+    let mut initial_balance = 0;
+
+    ParaB::execute_with(|| {
+        setup::fund_address(&BOB);
+        initial_balance = ParachainBalances::free_balance(&xc_contract);
+
+        let rs = register_name(&xc_contract, ALICE, "alice");
+        assert_eq!(rs, Ok(0)); // tid = 0
+
+        // Name is already claimed by the time this request reaches the hub
+        let rs = register_name(&xc_contract, BOB, "alice");
+        assert_eq!(rs, Ok(1)); // tid = 1
+    });
+
+    ParaB::execute_with(|| {
+        // BOB is refunded in full
+        assert_eq!(get_escrow(&xc_contract, 1), None);
+        let balance = ParachainBalances::free_balance(&xc_contract);
+        assert_eq!(balance, initial_balance + 100);
+    });
+}
+
+#[test]
+fn write_status_is_reported() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        setup::fund_address(&BOB);
+
+        let rs = register_name(&xc_contract, ALICE, "alice");
+        assert_eq!(rs, Ok(0)); // tid = 0
+
+        // BOB does not own the name
+        let rs = transfer_name(&xc_contract, BOB, "alice", BOB);
+        assert_eq!(rs, Ok(1)); // tid = 1
+
+        let rs = get_ticket_status(&xc_contract, 0);
+        assert_eq!(rs, Ok(TicketStatus::AwaitingResponse));
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_ticket_status(&xc_contract, 0);
+        assert_eq!(rs, Ok(TicketStatus::Succeeded));
+
+        let rs = retrieve_write_outcome(&xc_contract, 0);
+        assert_eq!(rs, Ok(Ok(100))); // charged price
+
+        let not_owner = HandlerError::DomainService(ERR_NOT_OWNER);
+        let rs = get_ticket_status(&xc_contract, 1);
+        assert_eq!(rs, Ok(TicketStatus::Failed(not_owner)));
+    });
+}
+
+#[test]
+fn consumed_ticket_can_be_cleared() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        setup::fund_address(&BOB);
+
+        let rs = get_owner(&xc_contract, "alice");
+        assert_eq!(rs, Ok(0)); // tid = 0
+
+        // Cannot be cleared while awaiting response
+        let rs = clear_ticket(&xc_contract, ALICE, 0);
+        assert!(rs.is_err());
+    });
+
+    ParaB::execute_with(|| {
+        let rs = retrieve_owner(&xc_contract, 0);
+        assert_eq!(rs, Ok(None));
+
+        // Only the requester can clear the ticket
+        let rs = clear_ticket(&xc_contract, BOB, 0);
+        assert!(rs.is_err());
+
+        let rs = clear_ticket(&xc_contract, ALICE, 0);
+        assert_eq!(rs, Ok(()));
+
+        // Response is gone along with the ticket
+        let rs = retrieve_owner(&xc_contract, 0);
+        assert!(rs.is_err());
+    });
+}
+
+#[test]
+fn unanswered_ticket_expires() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        set_ticket_timeout(&xc_contract, 5);
+
+        let rs = register_name(&xc_contract, ALICE, "alice");
+        assert_eq!(rs, Ok(0)); // tid = 0
+
+        // Timeout elapses before the hub responds
+        parachain::System::set_block_number(6);
+        let rs = get_ticket_status(&xc_contract, 0);
+        assert_eq!(rs, Ok(TicketStatus::Expired));
+
+        // The hub may still apply the request, so the payment is not refunded on timeout
+        let rs = clear_ticket(&xc_contract, ALICE, 0);
+        assert_eq!(rs, Err(ERR_PAYMENT_IN_ESCROW));
+        assert_eq!(get_escrow(&xc_contract, 0), Some((ALICE, 100)));
+    });
+
+    ParaB::execute_with(|| {
+        // Late response settles the payment
+        let rs = get_ticket_status(&xc_contract, 0);
+        assert_eq!(rs, Ok(TicketStatus::Succeeded));
+        assert_eq!(get_escrow(&xc_contract, 0), None);
+
+        let rs = clear_ticket(&xc_contract, ALICE, 0);
+        assert_eq!(rs, Ok(()));
+    });
+}
+
+#[test]
+fn lost_response_is_resolved_by_admin() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        setup::fund_address(&BOB);
+        set_ticket_timeout(&xc_contract, 5);
+
+        // The hub can't decode the contract call, so no response is ever sent
+        set_chain_profiles(&xc_contract, DEFAULT_PROFILE, (40, 99, 31, 0, 8, 3));
+        let rs = register_name(&xc_contract, BOB, "bob");
+        assert_eq!(rs, Ok(0)); // tid = 0
+    });
+
+    ParaB::execute_with(|| {
+        // Can't be resolved while awaiting the response
+        let rs = resolve_escrow(&xc_contract, ALICE, 0, 100);
+        assert_eq!(rs, Err(ERR_AWAITING_RESPONSE));
+
+        parachain::System::set_block_number(6);
+        let rs = resolve_escrow(&xc_contract, BOB, 0, 100);
+        assert_eq!(rs, Err(ERR_NOT_ADMIN));
+        let rs = resolve_escrow(&xc_contract, ALICE, 0, 101);
+        assert_eq!(rs, Err(ERR_INSUFFICIENT_FUNDS));
+
+        let rs = resolve_escrow(&xc_contract, ALICE, 0, 100);
+        assert_eq!(rs, Ok(()));
+        assert_eq!(get_escrow(&xc_contract, 0), None);
+
+        let refunded = XcDomainServiceEvent::PaymentRefunded {
+            ticket_id: 0,
+            to: BOB,
+            amount: 100,
+        };
+        let events = contract_events::<XcDomainServiceEvent>(&xc_contract);
+        assert_eq!(events.last(), Some(&refunded));
+
+        let rs = clear_ticket(&xc_contract, BOB, 0);
+        assert_eq!(rs, Ok(()));
+    });
+}
+
+#[test]
+fn tickets_are_listed_per_requester() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        setup::fund_address(&BOB);
+
+        get_owner(&xc_contract, "alice").unwrap(); // tid = 0
+        register_name(&xc_contract, BOB, "bob").unwrap(); // tid = 1
+        get_address(&xc_contract, "alice").unwrap(); // tid = 2
+        get_owner(&xc_contract, "bob").unwrap(); // tid = 3
+
+        assert_eq!(get_my_tickets(&xc_contract, &ALICE, 0, 10), vec![0, 2, 3]);
+        assert_eq!(get_my_tickets(&xc_contract, &ALICE, 1, 1), vec![2]);
+        assert_eq!(get_my_tickets(&xc_contract, &BOB, 0, 10), vec![1]);
+    });
+
+    ParaB::execute_with(|| {
+        // Cleared tickets are no longer listed, and the others keep their place
+        clear_ticket(&xc_contract, ALICE, 2).unwrap();
+        assert_eq!(get_my_tickets(&xc_contract, &ALICE, 0, 10), vec![0, 3]);
+        assert_eq!(get_my_tickets(&xc_contract, &ALICE, 0, 1), vec![0]);
+        assert_eq!(get_my_tickets(&xc_contract, &ALICE, 1, 1), vec![]);
+        assert_eq!(get_my_tickets(&xc_contract, &ALICE, 2, 1), vec![3]);
+        assert_eq!(get_my_ticket_slots(&xc_contract, &ALICE), 3);
+
+        clear_ticket(&xc_contract, ALICE, 0).unwrap();
+        assert_eq!(get_my_tickets(&xc_contract, &ALICE, 2, 1), vec![3]);
+
+        // Only the last slot is given back
+        clear_ticket(&xc_contract, ALICE, 3).unwrap();
+        assert_eq!(get_my_ticket_slots(&xc_contract, &ALICE), 2);
+        assert_eq!(get_my_tickets(&xc_contract, &ALICE, 0, 10), vec![]);
+
+        get_owner(&xc_contract, "alice").unwrap(); // tid = 4
+        assert_eq!(get_my_tickets(&xc_contract, &ALICE, 0, 10), vec![4]);
+        assert_eq!(get_my_tickets(&xc_contract, &ALICE, 2, 1), vec![4]);
+    });
+}
+
+#[test]
+fn private_responses_are_readable_by_requester_only() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        setup::fund_address(&BOB);
+        set_private_responses(&xc_contract, ALICE, true);
+
+        let rs = get_owner(&xc_contract, "alice");
+        assert_eq!(rs, Ok(0)); // tid = 0
+    });
+
+    ParaB::execute_with(|| {
+        assert!(read_raw_response(&xc_contract, ALICE, 0).is_some());
+        assert_eq!(read_raw_response(&xc_contract, BOB, 0), None);
+    });
+}
+
+#[test]
+fn response_is_forwarded_to_callback() {
+    MockNet::reset();
+    let (_, xcm_handler, xc_contract) = setup::setup();
+    let xcm_handler_soac = sibling_account_account_id(1, xcm_handler.clone());
+
+    ParaB::execute_with(|| {
+        // Any contract message works as a receiver, so another xc-contract instance is used
+        let receiver = setup::deploy_xc_contract(&BOB, &xcm_handler, &xcm_handler_soac);
+        let selector = get_selector("get_ticket_count");
+
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        let rs = get_owner_with_callback(&xc_contract, "alice", &receiver, selector);
+        assert_eq!(rs, Ok(1)); // tid = 1
+
+        // BOB is not a contract, so the callback cannot be delivered
+        let rs = get_owner_with_callback(&xc_contract, "alice", &BOB, selector);
+        assert_eq!(rs, Ok(2)); // tid = 2
+
+        let rs = get_callback(&xc_contract, 1).map(|cb| cb.status);
+        assert_eq!(rs, Some(CallbackStatus::Pending));
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_callback(&xc_contract, 1).map(|cb| cb.status);
+        assert_eq!(rs, Some(CallbackStatus::Delivered));
+
+        // Failed callback is recorded, while the response remains readable
+        let rs = get_callback(&xc_contract, 2).map(|cb| cb.status);
+        assert_eq!(rs, Some(CallbackStatus::Failed));
+        let rs = retrieve_owner(&xc_contract, 2);
+        assert_eq!(rs, Ok(Some(hub_account(ALICE))));
+    });
+}
+
+#[test]
+fn callbacks_cannot_revert_the_response() {
+    MockNet::reset();
+    let (_, xcm_handler, xc_contract) = setup::setup();
+    let xcm_handler_soac = sibling_account_account_id(1, xcm_handler.clone());
+    let receiver =
+        ParaB::execute_with(|| setup::deploy_xc_contract(&BOB, &xcm_handler, &xcm_handler_soac));
+    let selector = get_selector("get_ticket_count");
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        // The callback runs out of gas
+        set_callback_gas_limit(&xc_contract, 1_000, 32 * 1024);
+        let rs = get_owner_with_callback(&xc_contract, "alice", &receiver, selector);
+        assert_eq!(rs, Ok(1)); // tid = 1
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_callback(&xc_contract, 1).map(|cb| cb.status);
+        assert_eq!(rs, Some(CallbackStatus::Failed));
+        let rs = retrieve_owner(&xc_contract, 1);
+        assert_eq!(rs, Ok(Some(hub_account(ALICE))));
+
+        // A limit above the remaining gas is capped, rather than trapping `accept_response`
+        set_callback_gas_limit(&xc_contract, u64::MAX, 32 * 1024);
+        let rs = get_owner_with_callback(&xc_contract, "alice", &receiver, selector);
+        assert_eq!(rs, Ok(2)); // tid = 2
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_callback(&xc_contract, 2).map(|cb| cb.status);
+        assert_eq!(rs, Some(CallbackStatus::Delivered));
+        let rs = retrieve_owner(&xc_contract, 2);
+        assert_eq!(rs, Ok(Some(hub_account(ALICE))));
+    });
+}
+
+#[test]
+fn batch_resolution_works() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+        set_address(&xc_contract, ALICE, "alice", &Address::V1(0, None, ALICE)).unwrap(); // tid = 1
+
+        // Empty and oversized batches are rejected upfront
+        let rs = get_owners(&xc_contract, &[]);
+        assert_eq!(rs, Err(ERR_INVALID_BATCH_SIZE));
+
+        let rs = get_owners(&xc_contract, &["alice"; 21]);
+        assert_eq!(rs, Err(ERR_INVALID_BATCH_SIZE));
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_owners(&xc_contract, &["alice", "nobody"]);
+        assert_eq!(rs, Ok(2)); // tid = 2
+
+        let rs = get_addresses(&xc_contract, &["nobody", "alice"]);
+        assert_eq!(rs, Ok(3)); // tid = 3
+    });
+
+    ParaB::execute_with(|| {
+        let rs = retrieve_owners(&xc_contract, 2);
+        assert_eq!(rs, Ok(vec![Some(hub_account(ALICE)), None]));
+
+        let account = Junction::AccountId32 {
+            network: None,
+            id: ALICE.into(),
+        };
+        let loc = VersionedMultiLocation::V3(account.into());
+        let rs = retrieve_addresses(&xc_contract, 3);
+        assert_eq!(rs, Ok(vec![None, Some(loc)]));
+    });
+}
+
+#[test]
+fn unanchorable_address_is_reported_as_failed_read() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaA::execute_with(|| set_unanchorable_address(&state_manager, "deep"));
+
+    ParaB::execute_with(|| {
+        let rs = get_address(&xc_contract, "deep");
+        assert_eq!(rs, Ok(0)); // tid = 0
+    });
+
+    ParaB::execute_with(|| {
+        let failed = TicketStatus::Failed(HandlerError::ReanchoringFailed);
+        assert_eq!(get_ticket_status(&xc_contract, 0), Ok(failed));
+        let rs = retrieve_address(&xc_contract, 0);
+        assert_eq!(rs, Err(ERR_REQUEST_FAILED));
+
+        // Other getters report the failed read as well, rather than a mismatch
+        let rs = retrieve_owner(&xc_contract, 0);
+        assert_eq!(rs, Err(ERR_REQUEST_FAILED));
+        let rs = retrieve_records(&xc_contract, 0);
+        assert_eq!(rs, Err(ERR_REQUEST_FAILED));
+        let rs = retrieve_write_outcome(&xc_contract, 0);
+        assert_eq!(rs, Err(ERR_REQUEST_FAILED));
+    });
+}
+
+#[test]
+fn unanchorable_addresses_are_resolved_as_none_in_batches() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaA::execute_with(|| set_unanchorable_address(&state_manager, "deep"));
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+        let address = Address::V1(0, None, ALICE);
+        set_address(&xc_contract, ALICE, "alice", &address).unwrap(); // tid = 1
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_addresses(&xc_contract, &["deep", "alice"]);
+        assert_eq!(rs, Ok(2)); // tid = 2
+    });
+
+    ParaB::execute_with(|| {
+        let account = Junction::AccountId32 {
+            network: None,
+            id: ALICE.into(),
+        };
+        let loc = VersionedMultiLocation::V3(account.into());
+        let rs = retrieve_addresses(&xc_contract, 2);
+        assert_eq!(rs, Ok(vec![None, Some(loc)]));
+    });
+}
+
+#[test]
+fn requests_are_routed_to_configured_hub() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        // There is no hub on ParaId 3
+        set_locations(&xc_contract, 2, 3);
+        let rs = get_owner(&xc_contract, "alice");
+        assert_eq!(rs, Err(ERR_CALL_RUNTIME_FAILED));
+
+        set_locations(&xc_contract, 2, 1);
+        let rs = get_owner(&xc_contract, "alice");
+        assert_eq!(rs, Ok(1)); // tid = 1
+    });
+
+    ParaB::execute_with(|| {
+        let rs = retrieve_owner(&xc_contract, 1);
+        assert_eq!(rs, Ok(Some(hub_account(ALICE))));
+    });
+}
+
+#[test]
+fn calls_are_encoded_with_chain_profiles() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        // The hub can't decode the contract call with a wrong call index
+        set_chain_profiles(&xc_contract, DEFAULT_PROFILE, (40, 99, 31, 0, 8, 3));
+        let rs = get_owner(&xc_contract, "alice");
+        assert_eq!(rs, Ok(1)); // tid = 1
+
+        set_chain_profiles(&xc_contract, DEFAULT_PROFILE, DEFAULT_PROFILE);
+        let rs = get_owner(&xc_contract, "alice");
+        assert_eq!(rs, Ok(2)); // tid = 2
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_ticket_status(&xc_contract, 1);
+        assert_eq!(rs, Ok(TicketStatus::AwaitingResponse));
+
+        let rs = retrieve_owner(&xc_contract, 2);
+        assert_eq!(rs, Ok(Some(hub_account(ALICE))));
+    });
+}
+
+#[test]
+fn paid_execution_withdraws_fee_from_sender_account() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        // The contract's account on the hub can't afford the fee
+        let fee_mode = XcmFeeMode::Paid {
+            asset: None, // Relay-chain's native asset
+            amount: INITIAL_BALANCE * 2,
+        };
+        set_fee_mode(&xc_contract, fee_mode);
+        let rs = get_owner(&xc_contract, "alice");
+        assert_eq!(rs, Ok(1)); // tid = 1
+
+        let fee_mode = XcmFeeMode::Paid {
+            asset: None,
+            amount: 1_000,
+        };
+        set_fee_mode(&xc_contract, fee_mode);
+        let rs = get_owner(&xc_contract, "alice");
+        assert_eq!(rs, Ok(2)); // tid = 2
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_ticket_status(&xc_contract, 1);
+        assert_eq!(rs, Ok(TicketStatus::AwaitingResponse));
+
+        let rs = retrieve_owner(&xc_contract, 2);
+        assert_eq!(rs, Ok(Some(hub_account(ALICE))));
+    });
+}
+
+#[test]
+fn fee_quote_works() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        // Hub: 1 unit per ref-time weight, ParaB: 1 unit per 2 ref-time weight,
+        // proof-size is free
+        let fee_estimator = ((1_000_000_000, 0), 500_000_000_000, 0);
+        let hub_fee_estimator = ((1_000_000_000, 0), 1_000_000_000_000, 0);
+        set_fee_estimators(&xc_contract, fee_estimator, hub_fee_estimator);
+
+        // 3 instructions + default gas limit (10_000_000_000) + additional gas (1_000_000_000)
+        // for both the request (14_000_000_000) and the response (7_000_000_000)
+        let rs = quote_fee(&xc_contract, Operation::RegisterName);
+        assert_eq!(rs, 21_000_000_000);
+
+        // The gas limit of the operation only applies to the request
+        set_operation_gas_limit(&xc_contract, Operation::GetOwner, Some((1_000_000_000, 0)));
+        let rs = quote_fee(&xc_contract, Operation::GetOwner);
+        assert_eq!(rs, 12_000_000_000);
+
+        // Paid execution takes 6 instructions
+        let fee_mode = XcmFeeMode::Paid {
+            asset: None,
+            amount: 1_000,
+        };
+        set_fee_mode(&xc_contract, fee_mode);
+        let rs = quote_fee(&xc_contract, Operation::RegisterName);
+        assert_eq!(rs, 25_500_000_000);
+    });
+}
+
+#[test]
+fn aliasing_is_opt_in_per_origin_chain() {
+    MockNet::reset();
+    let (state_manager, xcm_handler, xc_contract) = setup::setup();
+
+    ParaA::execute_with(|| set_alias_mode(&xcm_handler, 2, true));
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+    });
+
+    // Accounts of ParaB are used as-is
+    ParaA::execute_with(|| {
+        let rs = native_dns::get_owner(&state_manager, "alice");
+        assert_eq!(rs, Some(ALICE));
+
+        set_alias_mode(&xcm_handler, 2, false);
+    });
+
+    ParaB::execute_with(|| {
+        // ALICE of ParaB no longer owns the name on the hub
+        let rs = transfer_name(&xc_contract, ALICE, "alice", BOB);
+        assert_eq!(rs, Ok(1)); // tid = 1
+    });
+
+    ParaB::execute_with(|| {
+        let rs = get_ticket_status(&xc_contract, 1);
+        let err = HandlerError::DomainService(ERR_NOT_OWNER);
+        assert_eq!(rs, Ok(TicketStatus::Failed(err)));
+    });
+}
+
+#[test]
+fn hostile_locations_are_reported_as_failed_writes() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+
+        // Points beyond the relay chain
+        let address = Address::V1(5, None, ALICE);
+        let rs = set_address(&xc_contract, ALICE, "alice", &address);
+        assert_eq!(rs, Ok(1)); // tid = 1
+
+        // Points to a chain unknown to the hub
+        let address = Address::V1(1, Some(99), ALICE);
+        let rs = set_address(&xc_contract, ALICE, "alice", &address);
+        assert_eq!(rs, Ok(2)); // tid = 2
+
+        // Points to an account on the hub
+        let address = Address::V1(1, Some(1), BOB);
+        let rs = set_address(&xc_contract, ALICE, "alice", &address);
+        assert_eq!(rs, Ok(3)); // tid = 3
+    });
+
+    ParaB::execute_with(|| {
+        let failed = Ok(TicketStatus::Failed(HandlerError::ReanchoringFailed));
+        assert_eq!(get_ticket_status(&xc_contract, 1), failed);
+        assert_eq!(get_ticket_status(&xc_contract, 2), failed);
+        let rs = get_ticket_status(&xc_contract, 3);
+        assert_eq!(rs, Ok(TicketStatus::Succeeded));
+    });
+
+    ParaA::execute_with(|| {
+        let account = Junction::AccountId32 {
+            network: None,
+            id: BOB.into(),
+        };
+        let loc = VersionedMultiLocation::V3(account.into());
+
+        let rs = native_dns::get_address(&state_manager, "alice");
+        assert_eq!(rs, Some(loc));
+    });
+}
+
+#[test]
+fn fee_split_only_applies_to_native_payments() {
+    MockNet::reset();
+    let (state_manager, _, xc_contract) = setup::setup();
+    let (mut balance, mut withdrawable) = (0, 0);
+
+    ParaA::execute_with(|| {
+        native_dns::set_fee_split(&state_manager, (50, 0, 50)).unwrap();
+        balance = ParachainBalances::free_balance(&state_manager);
+        withdrawable = native_dns::get_withdrawable_balance(&state_manager);
+    });
+
+    ParaB::execute_with(|| {
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+    });
+
+    // The payment is held by the xc-contract, so nothing is locked on the hub
+    ParaA::execute_with(|| {
+        let rs = native_dns::get_owner(&state_manager, "alice");
+        assert_eq!(rs, Some(hub_account(ALICE)));
+        assert_eq!(ParachainBalances::free_balance(&state_manager), balance);
+        let rs = native_dns::get_withdrawable_balance(&state_manager);
+        assert_eq!(rs, withdrawable);
+    });
+}
+
+#[test]
+fn fees_can_be_withdrawn_and_forwarded_to_the_hub() {
+    MockNet::reset();
+    let (_, _, xc_contract) = setup::setup();
+    let mut initial_balance = 0;
+
+    ParaA::execute_with(|| {
+        initial_balance = ParachainBalances::free_balance(&BOB);
+    });
+
+    ParaB::execute_with(|| {
+        let balance = ParachainBalances::free_balance(&xc_contract);
+
+        // Payments held in escrow can't be withdrawn nor forwarded
+        register_name(&xc_contract, ALICE, "alice").unwrap(); // tid = 0
+        let rs = withdraw(&xc_contract, balance + 1, BOB);
+        assert_eq!(rs, Err(ERR_INSUFFICIENT_FUNDS));
+        let rs = forward_fees(&xc_contract, balance + 1, BOB);
+        assert_eq!(rs, Err(ERR_INSUFFICIENT_FUNDS));
+
+        // ParaB uses the relay token as its currency, and weighs XCM instructions (1, 1)
+        set_native_asset(&xc_contract, None);
+        set_xcm_instruction_weight(&xc_contract, 1, 1);
+    });
+
+    ParaB::execute_with(|| {
+        // The price is retained once the hub responds
+        let rs = withdraw(&xc_contract, 40, BOB);
+        assert_eq!(rs, Ok(()));
+
+        let balance = ParachainBalances::free_balance(&xc_contract);
+        let rs = forward_fees(&xc_contract, 60, BOB);
+        assert_eq!(rs, Ok(()));
+        assert_eq!(ParachainBalances::free_balance(&xc_contract), balance - 60);
+
+        let forwarded = XcDomainServiceEvent::FeesForwarded {
+            asset: None,
+            amount: 60,
+            beneficiary: BOB,
+        };
+        let events = contract_events::<XcDomainServiceEvent>(&xc_contract);
+        assert_eq!(events.last(), Some(&forwarded));
+    });
+
+    // The fees are withdrawn from ParaB's sovereign account on the relay chain, the reserve
+    Relay::execute_with(|| {
+        let balance = RelayChainBalances::free_balance(&child_account_id(2));
+        assert_eq!(balance, INITIAL_BALANCE - 60);
+    });
+
+    // BOB receives the forwarded fees on the hub, less the execution fees
+    ParaA::execute_with(|| {
+        let balance = ParachainBalances::free_balance(&BOB);
+        assert!(balance > initial_balance && balance <= initial_balance + 60);
     });
 }
@@ -19,6 +19,14 @@ pub type ParachainClient = subxt::OnlineClient<subxt::SubstrateConfig>;
 
 pub const CUSTOM_WT: Option<(u64, u64)> = Some((11_000_000_000, 140_000)); // Update this if Xcm.success but no Contract.Called event
 
+// (weight_per_instruction, units_per_second, units_per_mb) used to quote fees.
+// Update this to match the XCM config of the parachains
+pub const FEE_ESTIMATOR: ((u64, u64), u128, u128) = (
+    (1_000_000_000, 65_536),
+    1_000_000_000_000,
+    1_000_000_000_000,
+);
+
 async fn deploy_state_manager(
     client: &ParachainClient,
     admin: &AccountId32,
@@ -40,13 +48,24 @@ async fn deploy_xcm_handler(
     let code = std::fs::read("./artefacts/xcm_handler.wasm").expect("cound not find wasm blob");
 
     let sel_constructor = get_selector("new");
-    let payload = (sel_constructor, admin, state_manager, CUSTOM_WT).encode(); // (selector, admin, state_manager, custom_wt)
+    let universal_location = Some(1u32); // ParaA
+
+    // (selector, admin, state_manager, universal_location, custom_wt)
+    let payload = (
+        sel_constructor,
+        admin,
+        state_manager,
+        universal_location,
+        CUSTOM_WT,
+    )
+        .encode();
 
     deploy_contract(client, code, payload, dev::alice()).await
 }
 
 async fn deploy_xc_contract(
     client: &ParachainClient,
+    admin: &AccountId32,
     xcm_handler: &AccountId32,
     xcm_handler_soac: &AccountId32,
 ) -> Result<AccountId32, Box<dyn std::error::Error>> {
@@ -54,7 +73,22 @@ async fn deploy_xc_contract(
         std::fs::read("./artefacts/xc_domain_service.wasm").expect("cound not find wasm blob");
 
     let sel_constructor = get_selector("new");
-    let payload = (sel_constructor, xcm_handler, xcm_handler_soac, CUSTOM_WT).encode(); // (selector, xcm_handler, xcm_handler_soac, custom_wt)
+    let (universal_location, hub_location) = (Some(2u32), Some(1u32)); // (ParaB, ParaA)
+
+    // (selector, admin, xcm_handler, xcm_handler_soac, universal_location, hub_location, custom_wt,
+    //  fee_estimator, hub_fee_estimator)
+    let payload = (
+        sel_constructor,
+        admin,
+        xcm_handler,
+        xcm_handler_soac,
+        universal_location,
+        hub_location,
+        CUSTOM_WT,
+        FEE_ESTIMATOR,
+        FEE_ESTIMATOR,
+    )
+        .encode();
 
     deploy_contract(client, code, payload, dev::alice()).await
 }
@@ -108,7 +142,7 @@ async fn setup(
     println!("Linked the xcm-handler with domain-service successfully");
 
     // 3A. Deploy `xc_domain_service: xc-contract`
-    let xc_contract = deploy_xc_contract(para_b, &xcm_handler, &xcm_handler_soac).await?;
+    let xc_contract = deploy_xc_contract(para_b, &alice, &xcm_handler, &xcm_handler_soac).await?;
     let xc_contract_soac = sibling_account_account_id(2, &xc_contract);
     println!(
         "Xc-domain-service deployed on ParaB with Address: {:}",